@@ -0,0 +1,38 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use todo_tree::parser::TodoParser;
+use todo_tree_core::tags::default_tag_names;
+
+/// A Rust-like source of `lines` lines with a tag on every `every`th line.
+fn source(lines: usize, every: usize) -> String {
+    (0..lines)
+        .map(|i| match i % every {
+            0 => format!("    // TODO(alice): handle case {} see #{}\n", i, i),
+            1 => "    /* a block comment mentioning nothing */\n".to_string(),
+            _ => format!("    let value_{} = compute(\"// not a TODO\", {});\n", i, i),
+        })
+        .collect()
+}
+
+fn parse_content(c: &mut Criterion) {
+    let parser = TodoParser::new(&default_tag_names(), true);
+    let sparse = source(5_000, 200);
+    let dense = source(5_000, 4);
+
+    c.bench_function("parse_content/sparse", |b| {
+        b.iter(|| parser.parse_content(black_box(&sparse)))
+    });
+    c.bench_function("parse_content/dense", |b| {
+        b.iter(|| parser.parse_content(black_box(&dense)))
+    });
+
+    let mut many_tags: Vec<String> = (0..500).map(|i| format!("TAG{:03}X", i)).collect();
+    many_tags.extend(default_tag_names());
+    let many = TodoParser::new(&many_tags, false);
+    c.bench_function("parse_content/500_tags", |b| {
+        b.iter(|| many.parse_content(black_box(&sparse)))
+    });
+}
+
+criterion_group!(benches, parse_content);
+criterion_main!(benches);
@@ -1,6 +1,6 @@
 fn main() {
     if let Err(err) = todo_tree::run() {
         eprintln!("Error: {:#}", err);
-        std::process::exit(1);
+        std::process::exit(todo_tree::exit_codes::for_error(&err));
     }
 }
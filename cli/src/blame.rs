@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::process::Command;
+use todo_tree_core::ScanResult;
+
+/// Author and time of the commit that last touched a line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct BlameLine {
+    author: String,
+    date: Option<DateTime<Utc>>,
+}
+
+/// Fill in `blame_author` and `blame_date` of every item from `git blame`,
+/// blaming only the matched lines. Files git cannot blame (untracked, outside
+/// a repository, or no `git` at all) are left untouched.
+pub fn annotate(result: &mut ScanResult) {
+    for (path, items) in result.files_map.iter_mut() {
+        let lines: BTreeSet<usize> = items.iter().map(|item| item.line).collect();
+        let Some(blame) = blame_lines(path, &lines) else {
+            continue;
+        };
+
+        for item in items {
+            if let Some(line) = blame.get(&item.line) {
+                item.blame_author = Some(line.author.clone());
+                item.blame_date = line.date;
+            }
+        }
+    }
+}
+
+fn blame_lines(path: &Path, lines: &BTreeSet<usize>) -> Option<HashMap<usize, BlameLine>> {
+    let mut command = Command::new("git");
+    command
+        .current_dir(path.parent()?)
+        .args(["blame", "--porcelain"]);
+    for line in lines {
+        command.arg("-L").arg(format!("{0},{0}", line));
+    }
+    let output = command.arg("--").arg(path.file_name()?).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_porcelain(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Map final line numbers to their commit's author and time. Commit details
+/// are only printed the first time a commit appears, so they are kept by sha.
+/// Lines that are not committed yet are left out.
+fn parse_porcelain(output: &str) -> HashMap<usize, BlameLine> {
+    let mut commits: HashMap<&str, BlameLine> = HashMap::new();
+    let mut lines = HashMap::new();
+    let mut current: Option<(&str, usize)> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            if let Some((sha, number)) = current.take()
+                && let Some(commit) = commits.get(sha)
+                && sha.bytes().any(|b| b != b'0')
+            {
+                lines.insert(number, commit.clone());
+            }
+        } else if let Some((sha, _)) = current {
+            let commit = commits.entry(sha).or_default();
+            if let Some(author) = line.strip_prefix("author ") {
+                commit.author = author.to_string();
+            } else if let Some(time) = line.strip_prefix("author-time ") {
+                commit.date = time
+                    .parse()
+                    .ok()
+                    .and_then(|secs| DateTime::from_timestamp(secs, 0));
+            }
+        } else {
+            // Header: `<sha> <original line> <final line> [<group size>]`.
+            let mut fields = line.split(' ');
+            if let (Some(sha), Some(_), Some(number)) =
+                (fields.next(), fields.next(), fields.next())
+                && let Ok(number) = number.parse()
+            {
+                commits.entry(sha).or_default();
+                current = Some((sha, number));
+            }
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TodoParser;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn git(dir: &Path, args: &[&str]) -> bool {
+        Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=Ada", "-c", "user.email=ada@example.com"])
+            .args(args)
+            .env("GIT_AUTHOR_DATE", "2024-01-02T03:04:05Z")
+            .env("GIT_COMMITTER_DATE", "2024-01-02T03:04:05Z")
+            .output()
+            .is_ok_and(|output| output.status.success())
+    }
+
+    #[test]
+    fn committed_lines_get_author_and_date() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let repo = std::env::temp_dir().join(format!("todo_blame_repo_{unique}"));
+        fs::create_dir_all(&repo).unwrap();
+        let committed = repo.join("lib.rs");
+        fs::write(&committed, "fn main() {}\n// TODO: committed\n").unwrap();
+        if !git(&repo, &["init", "-q"])
+            || !git(&repo, &["add", "lib.rs"])
+            || !git(&repo, &["commit", "-q", "--no-gpg-sign", "-m", "init"])
+        {
+            // No usable git here; nothing to check.
+            let _ = fs::remove_dir_all(&repo);
+            return;
+        }
+        let untracked = repo.join("new.rs");
+        fs::write(&untracked, "// TODO: untracked\n").unwrap();
+
+        let parser = TodoParser::new(&["TODO".to_string()], true);
+        let mut result = ScanResult::new(repo.clone());
+        for path in [&committed, &untracked] {
+            let items = parser.parse_file(path).unwrap();
+            result.add_file(path.clone(), items);
+        }
+        annotate(&mut result);
+        let _ = fs::remove_dir_all(&repo);
+
+        let item = &result.files_map[&committed][0];
+        assert_eq!(item.blame_author.as_deref(), Some("Ada"));
+        assert_eq!(item.blame_date, DateTime::from_timestamp(1_704_164_645, 0));
+        let item = &result.files_map[&untracked][0];
+        assert_eq!(item.blame_author, None);
+        assert_eq!(item.blame_date, None);
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::SystemTime;
+use todo_tree_core::TodoItem;
+
+/// Default cache file, relative to the scan root.
+pub const CACHE_FILE: &str = ".todo-tree-cache.json";
+
+/// Bumped when the layout changes, so older cache files are discarded.
+const CACHE_VERSION: u32 = 1;
+
+/// Items parsed by an earlier scan, reused for files whose modification time
+/// and size are unchanged.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScanCache {
+    version: u32,
+    /// [`crate::parser::TodoParser::cache_key`] of the parser that produced the items.
+    parser: String,
+    files: HashMap<PathBuf, CacheEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    modified: Option<SystemTime>,
+    size: u64,
+    items: Vec<TodoItem>,
+}
+
+impl ScanCache {
+    pub fn new(parser_key: &str) -> Self {
+        Self {
+            version: CACHE_VERSION,
+            parser: parser_key.to_string(),
+            files: HashMap::new(),
+        }
+    }
+
+    /// Read the cache at `path`. A missing or unreadable file, or one written
+    /// by another version or parser configuration, gives an empty cache.
+    pub fn load(path: &Path, parser_key: &str) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str::<Self>(&text).ok())
+            .filter(|cache| cache.version == CACHE_VERSION && cache.parser == parser_key)
+            .unwrap_or_else(|| Self::new(parser_key))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write cache file: {}", path.display()))
+    }
+}
+
+/// One scan's use of a [`ScanCache`]: lookups go to the previous cache, and
+/// every file seen is recorded in the next one, so deleted files drop out.
+pub struct CachedScan {
+    file: PathBuf,
+    previous: ScanCache,
+    next: Mutex<ScanCache>,
+    reparsed: AtomicUsize,
+}
+
+impl CachedScan {
+    /// Start a scan against the cache stored at `file`.
+    pub fn load(file: &Path, parser_key: &str) -> Self {
+        Self {
+            file: std::path::absolute(file).unwrap_or_else(|_| file.to_path_buf()),
+            previous: ScanCache::load(file, parser_key),
+            next: Mutex::new(ScanCache::new(parser_key)),
+            reparsed: AtomicUsize::new(0),
+        }
+    }
+
+    /// Whether `path` is the cache file itself, which is never scanned.
+    pub fn is_cache_file(&self, path: &Path) -> bool {
+        path == self.file
+    }
+
+    /// Items for `path`: cached if the file is unchanged, otherwise from `parse`.
+    pub fn items<F>(&self, path: &Path, parse: F) -> Result<Vec<TodoItem>>
+    where
+        F: FnOnce() -> Result<Vec<TodoItem>>,
+    {
+        let Ok(metadata) = path.metadata() else {
+            return parse();
+        };
+        let modified = metadata.modified().ok();
+        let size = metadata.len();
+
+        let items = match self.previous.files.get(path) {
+            Some(entry) if entry.modified == modified && entry.size == size => entry.items.clone(),
+            _ => {
+                self.reparsed.fetch_add(1, Ordering::Relaxed);
+                parse()?
+            }
+        };
+        self.next.lock().unwrap().files.insert(
+            path.to_path_buf(),
+            CacheEntry {
+                modified,
+                size,
+                items: items.clone(),
+            },
+        );
+        Ok(items)
+    }
+
+    /// Number of files parsed rather than taken from the cache.
+    pub fn reparsed(&self) -> usize {
+        self.reparsed.load(Ordering::Relaxed)
+    }
+
+    /// Write the entries recorded during this scan back to the cache file.
+    pub fn save(self) -> Result<()> {
+        self.next.into_inner().unwrap().save(&self.file)
+    }
+}
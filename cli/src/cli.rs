@@ -1,5 +1,8 @@
+use crate::printer::OutputFormat;
+use crate::thresholds::TagLimit;
 use clap::{Args, Parser, Subcommand, ValueHint};
 use std::path::PathBuf;
+use todo_tree_core::{Priority, SortKey};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -8,6 +11,7 @@ use std::path::PathBuf;
     version,
     about,
     long_about = None,
+    after_help = crate::exit_codes::HELP,
 )]
 pub struct Cli {
     #[command(flatten)]
@@ -15,6 +19,14 @@ pub struct Cli {
 
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// Print the JSON Schema of the `--json` output and exit.
+    #[arg(long)]
+    pub print_schema: bool,
+
+    /// Report parse throughput for a file and exit, for performance work.
+    #[arg(long, hide = true, value_name = "FILE")]
+    pub bench_parse: Option<PathBuf>,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -32,12 +44,26 @@ pub struct GlobalOptions {
         help = "Path to config file"
     )]
     pub config: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Allow running commands from the config (e.g. pre_scan_command)"
+    )]
+    pub allow_hooks: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Allow writing to the git repository (e.g. --format git-notes)"
+    )]
+    pub allow_git_write: bool,
 }
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum Commands {
     #[command(visible_alias = "s", about = "Scan files and print TODO matches")]
-    Scan(ScanArgs),
+    Scan(Box<ScanArgs>),
     #[command(visible_alias = "l", visible_alias = "ls", about = "List TODO matches")]
     List(ListArgs),
     #[command(visible_alias = "t", about = "Manage configured TODO tags")]
@@ -52,8 +78,12 @@ pub enum Commands {
 
 #[derive(Args, Debug, Clone)]
 pub struct ScanArgs {
-    #[arg(value_hint = ValueHint::AnyPath, help = "Path to scan (defaults to current directory)")]
-    pub path: Option<PathBuf>,
+    #[arg(
+        value_name = "PATH",
+        value_hint = ValueHint::AnyPath,
+        help = "Paths to scan (defaults to current directory)"
+    )]
+    pub paths: Vec<PathBuf>,
     #[arg(
         short,
         long,
@@ -75,10 +105,42 @@ pub struct ScanArgs {
         help = "File patterns to exclude (glob patterns, comma-separated)"
     )]
     pub exclude: Option<Vec<String>>,
+    #[arg(
+        long = "ext",
+        value_delimiter = ',',
+        help = "Only read files with these extensions (comma-separated, e.g. rs,py)"
+    )]
+    pub extensions: Option<Vec<String>>,
     #[arg(long, help = "Output results in JSON format")]
     pub json: bool,
     #[arg(long, help = "Print flat output without grouping by file")]
     pub flat: bool,
+    #[arg(long, help = "Print GitHub Actions workflow annotations")]
+    pub github: bool,
+    #[arg(long, help = "Output results as a SARIF 2.1.0 log")]
+    pub sarif: bool,
+    #[arg(long, help = "Output results as CSV for spreadsheets")]
+    pub csv: bool,
+    #[arg(long, help = "Output one JSON object per item per line (ndjson)")]
+    pub ndjson: bool,
+    #[arg(long, help = "Output a GitLab Code Quality report")]
+    pub codequality: bool,
+    #[arg(long, help = "Output a JUnit XML report for CI test viewers")]
+    pub junit: bool,
+    #[arg(
+        long,
+        value_enum,
+        help = "Output format (overrides --github, --sarif, --csv, --ndjson, --codequality, --junit, --json and --flat)"
+    )]
+    pub format: Option<OutputFormat>,
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        value_hint = ValueHint::FilePath,
+        help = "Write the output to FILE instead of stdout; its extension picks the format unless one is given"
+    )]
+    pub output: Option<PathBuf>,
     #[arg(
         short,
         long,
@@ -90,40 +152,276 @@ pub struct ScanArgs {
     pub follow_links: bool,
     #[arg(long, help = "Include hidden files and directories")]
     pub hidden: bool,
+    #[arg(long, help = "Do not skip paths listed in .todoignore files")]
+    pub no_todoignore: bool,
+    #[arg(
+        long,
+        help = "Report directories and files skipped for lack of permission"
+    )]
+    pub warn_unreadable: bool,
+    #[arg(
+        long,
+        help = "Report symlinks given as paths under the link, not the resolved target"
+    )]
+    pub keep_link_paths: bool,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Stop scanning once N items are found; which items depends on walk order"
+    )]
+    pub max_total_results: Option<usize>,
+    #[arg(
+        long,
+        help = "Collapse items with the same tag and message into one, noting where else they appear"
+    )]
+    pub dedup: bool,
+    #[arg(
+        long,
+        help = "Print only a hash of the found items, for cheap change detection"
+    )]
+    pub fingerprint: bool,
+    #[arg(
+        long,
+        conflicts_with = "fingerprint",
+        help = "Print only the totals and per-tag counts, without listing items"
+    )]
+    pub count: bool,
+    #[arg(
+        long,
+        help = "List the files that would be scanned, after all filters, without reading them"
+    )]
+    pub dry_run: bool,
+    #[arg(
+        long,
+        value_name = "FILE",
+        num_args = 0..=1,
+        default_missing_value = crate::cache::CACHE_FILE,
+        help = "Reuse items of unchanged files from a cache, relative to the scanned directory"
+    )]
+    pub cache: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "Add the git blame author and commit date of each item's line"
+    )]
+    pub blame: bool,
     #[arg(long, help = "Ignore case when matching tags")]
     pub ignore_case: bool,
+    #[arg(
+        long,
+        help = "Match tags in any case and count, per tag, how many deviate from its canonical casing"
+    )]
+    pub case_report: bool,
     #[arg(long, help = "Allow tags without a trailing colon")]
     pub no_require_colon: bool,
+    #[arg(long, help = "Match bare tags with no message after them")]
+    pub allow_empty: bool,
+    #[arg(
+        long,
+        help = "Match tags outside comments (e.g. inside string literals)"
+    )]
+    pub match_anywhere: bool,
+    #[arg(
+        long,
+        help = "Skip tags inside URLs, e.g. https://example.com/docs#TODO"
+    )]
+    pub skip_urls: bool,
+    #[arg(
+        long,
+        help = "Include the comment lines continuing each item in its body"
+    )]
+    pub multiline: bool,
+    #[arg(
+        long,
+        help = "Tokenize Rust, Python and JS/TS files and match only real comments"
+    )]
+    pub lex_comments: bool,
+    #[arg(
+        long,
+        help = "Read [P0]-[P4] or [high] markers after the tag as the item's priority"
+    )]
+    pub inline_priority: bool,
+    #[arg(
+        long,
+        help = "Print each match's raw line and captured groups to stderr, before any filtering"
+    )]
+    pub print_matched_tags: bool,
+    #[arg(
+        long,
+        value_name = "PRIORITY",
+        help = "Hide items below this priority (low, medium, high, critical or 1-4)"
+    )]
+    pub min_priority: Option<Priority>,
+    #[arg(
+        long,
+        visible_alias = "only",
+        value_name = "TAG",
+        help = "Only show items whose tag matches, e.g. FIXME or a glob like 'FIX*'"
+    )]
+    pub filter: Option<String>,
+    #[arg(
+        long,
+        help = "Only show items assigned to this author, e.g. TODO(john):"
+    )]
+    pub author: Option<String>,
+    #[arg(
+        long,
+        help = "Only show items whose due date, e.g. TODO(2024-12-31):, has passed"
+    )]
+    pub overdue: bool,
+    #[arg(long, help = "Show items whose ids are listed in .todo-resolved")]
+    pub show_resolved: bool,
+    #[arg(long, value_name = "N", help = "Show a random sample of N items")]
+    pub sample: Option<usize>,
+    #[arg(
+        long,
+        requires = "sample",
+        help = "Seed for --sample so the same items are picked every run"
+    )]
+    pub seed: Option<u64>,
+    #[arg(
+        long,
+        help = "Step through items and record the ones you mark resolved in .todo-resolved"
+    )]
+    pub interactive_resolve: bool,
+    #[arg(
+        long,
+        conflicts_with = "interactive_resolve",
+        help = "Rescan and reprint whenever a scanned file changes"
+    )]
+    pub watch: bool,
     #[arg(long, default_value = "file", help = "Sort order for results")]
     pub sort: SortOrder,
     #[arg(long, help = "Group output by tag")]
     pub group_by_tag: bool,
+    #[arg(long, help = "Show item counts on each node of --format ascii-tree")]
+    pub show_counts: bool,
+    #[arg(long, help = "Always print paths with `/` separators, even on Windows")]
+    pub relative_paths_posix: bool,
+    #[arg(long, help = "Show each tag's description next to its items")]
+    pub with_description: bool,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Count tabs as N columns when reporting an item's column"
+    )]
+    pub tab_width: Option<usize>,
+    #[arg(
+        long,
+        value_name = "N",
+        default_value = "0",
+        help = "Show N lines of source before and after each item"
+    )]
+    pub context: usize,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Show only the first N items of each file"
+    )]
+    pub head: Option<usize>,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Show only the last N items of each file"
+    )]
+    pub tail: Option<usize>,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Show only the first N items after sorting, noting how many more there are"
+    )]
+    pub limit: Option<usize>,
+    #[arg(
+        long,
+        value_name = "N",
+        visible_alias = "max-allowed",
+        help = "Exit with code 1 if more than N items are found"
+    )]
+    pub max_total: Option<usize>,
+    #[arg(long, help = "Exit with code 1 if any item is found")]
+    pub fail_on_found: bool,
+    #[arg(
+        long,
+        value_name = "TAG=N",
+        help = "Exit with code 1 if TAG appears more than N times (repeatable)"
+    )]
+    pub max_tag: Vec<TagLimit>,
 }
 
 impl Default for ScanArgs {
     fn default() -> Self {
         Self {
-            path: None,
+            paths: Vec::new(),
             tags: None,
             include: None,
             exclude: None,
+            extensions: None,
             json: false,
             flat: false,
+            github: false,
+            sarif: false,
+            csv: false,
+            ndjson: false,
+            codequality: false,
+            junit: false,
+            format: None,
+            output: None,
             depth: 0,
             follow_links: false,
+            no_todoignore: false,
+            warn_unreadable: false,
+            keep_link_paths: false,
+            max_total_results: None,
+            dedup: false,
+            fingerprint: false,
+            count: false,
+            dry_run: false,
+            cache: None,
+            blame: false,
             hidden: false,
             ignore_case: false,
+            case_report: false,
             no_require_colon: false,
+            allow_empty: false,
+            match_anywhere: false,
+            skip_urls: false,
+            multiline: false,
+            lex_comments: false,
+            inline_priority: false,
+            print_matched_tags: false,
+            min_priority: None,
+            filter: None,
+            author: None,
+            overdue: false,
+            show_resolved: false,
+            sample: None,
+            seed: None,
+            interactive_resolve: false,
+            watch: false,
             sort: SortOrder::File,
             group_by_tag: false,
+            show_counts: false,
+            relative_paths_posix: false,
+            with_description: false,
+            tab_width: None,
+            context: 0,
+            head: None,
+            limit: None,
+            tail: None,
+            max_total: None,
+            fail_on_found: false,
+            max_tag: Vec::new(),
         }
     }
 }
 
 #[derive(Args, Debug, Clone, Default)]
 pub struct ListArgs {
-    #[arg(value_hint = ValueHint::AnyPath, help = "Path to scan (defaults to current directory)")]
-    pub path: Option<PathBuf>,
+    #[arg(
+        value_name = "PATH",
+        value_hint = ValueHint::AnyPath,
+        help = "Paths to scan (defaults to current directory)"
+    )]
+    pub paths: Vec<PathBuf>,
     #[arg(
         short,
         long,
@@ -147,12 +445,102 @@ pub struct ListArgs {
     pub exclude: Option<Vec<String>>,
     #[arg(long, help = "Output results in JSON format")]
     pub json: bool,
-    #[arg(long, help = "Filter results by a specific tag")]
+    #[arg(long, help = "Print GitHub Actions workflow annotations")]
+    pub github: bool,
+    #[arg(long, help = "Output results as a SARIF 2.1.0 log")]
+    pub sarif: bool,
+    #[arg(long, help = "Output results as CSV for spreadsheets")]
+    pub csv: bool,
+    #[arg(long, help = "Output one JSON object per item per line (ndjson)")]
+    pub ndjson: bool,
+    #[arg(long, help = "Output a GitLab Code Quality report")]
+    pub codequality: bool,
+    #[arg(long, help = "Output a JUnit XML report for CI test viewers")]
+    pub junit: bool,
+    #[arg(
+        long,
+        value_enum,
+        help = "Output format (overrides --github, --sarif, --csv, --ndjson, --codequality, --junit and --json)"
+    )]
+    pub format: Option<OutputFormat>,
+    #[arg(
+        long,
+        visible_alias = "only",
+        value_name = "TAG",
+        help = "Only show items whose tag matches, e.g. FIXME or a glob like 'FIX*'"
+    )]
     pub filter: Option<String>,
+    #[arg(
+        long,
+        help = "Only show items assigned to this author, e.g. TODO(john):"
+    )]
+    pub author: Option<String>,
+    #[arg(
+        long,
+        help = "Only show items whose due date, e.g. TODO(2024-12-31):, has passed"
+    )]
+    pub overdue: bool,
+    #[arg(long, help = "Show items whose ids are listed in .todo-resolved")]
+    pub show_resolved: bool,
+    #[arg(
+        long,
+        value_name = "PRIORITY",
+        help = "Hide items below this priority (low, medium, high, critical or 1-4)"
+    )]
+    pub min_priority: Option<Priority>,
     #[arg(long, help = "Ignore case when matching tags")]
     pub ignore_case: bool,
     #[arg(long, help = "Allow tags without a trailing colon")]
     pub no_require_colon: bool,
+    #[arg(long, help = "Match bare tags with no message after them")]
+    pub allow_empty: bool,
+    #[arg(
+        long,
+        help = "Match tags outside comments (e.g. inside string literals)"
+    )]
+    pub match_anywhere: bool,
+    #[arg(
+        long,
+        help = "Skip tags inside URLs, e.g. https://example.com/docs#TODO"
+    )]
+    pub skip_urls: bool,
+    #[arg(
+        long,
+        help = "Include the comment lines continuing each item in its body"
+    )]
+    pub multiline: bool,
+    #[arg(
+        long,
+        help = "Tokenize Rust, Python and JS/TS files and match only real comments"
+    )]
+    pub lex_comments: bool,
+    #[arg(
+        long,
+        help = "Read [P0]-[P4] or [high] markers after the tag as the item's priority"
+    )]
+    pub inline_priority: bool,
+    #[arg(
+        long,
+        help = "Print each match's raw line and captured groups to stderr, before any filtering"
+    )]
+    pub print_matched_tags: bool,
+    #[arg(long, help = "Always print paths with `/` separators, even on Windows")]
+    pub relative_paths_posix: bool,
+    #[arg(long, help = "Show each tag's description next to its items")]
+    pub with_description: bool,
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Count tabs as N columns when reporting an item's column"
+    )]
+    pub tab_width: Option<usize>,
+    #[arg(
+        long,
+        value_name = "N",
+        default_value = "0",
+        help = "Show N lines of source before and after each item"
+    )]
+    pub context: usize,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -227,13 +615,29 @@ pub struct StatsArgs {
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
 pub enum SortOrder {
-    #[value(name = "file", help = "Sort by file path")]
+    #[value(name = "file", alias = "path", help = "Sort by file path")]
     #[default]
     File,
     #[value(name = "line", help = "Sort by line number")]
     Line,
     #[value(name = "priority", help = "Sort by tag priority")]
     Priority,
+    #[value(name = "tag", help = "Sort by tag name")]
+    Tag,
+    #[value(name = "age", help = "Sort by git commit date, oldest first")]
+    Age,
+}
+
+impl From<SortOrder> for SortKey {
+    fn from(order: SortOrder) -> Self {
+        match order {
+            SortOrder::File => SortKey::Path,
+            SortOrder::Line => SortKey::Line,
+            SortOrder::Priority => SortKey::Priority,
+            SortOrder::Tag => SortKey::Tag,
+            SortOrder::Age => SortKey::Age,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
@@ -249,21 +653,115 @@ impl Cli {
     pub fn get_command(&self) -> Commands {
         self.command
             .clone()
-            .unwrap_or_else(|| Commands::Scan(ScanArgs::default()))
+            .unwrap_or_else(|| Commands::Scan(Box::default()))
+    }
+}
+
+impl ScanArgs {
+    /// The format asked for on the command line: `--format`, then the
+    /// shorthand flags in the order `--github`, `--sarif`, `--csv`,
+    /// `--ndjson`, `--codequality`, `--junit`, `--json`, `--flat`.
+    pub fn requested_format(&self) -> Option<OutputFormat> {
+        if let Some(format) = self.format {
+            Some(format)
+        } else if self.github {
+            Some(OutputFormat::Github)
+        } else if self.sarif {
+            Some(OutputFormat::Sarif)
+        } else if self.csv {
+            Some(OutputFormat::Csv)
+        } else if self.ndjson {
+            Some(OutputFormat::Ndjson)
+        } else if self.codequality {
+            Some(OutputFormat::Codequality)
+        } else if self.junit {
+            Some(OutputFormat::Junit)
+        } else if self.json {
+            Some(OutputFormat::Json)
+        } else if self.flat {
+            Some(OutputFormat::Flat)
+        } else {
+            None
+        }
+    }
+
+    /// The paths to scan, defaulting to the current directory.
+    pub fn scan_paths(&self) -> Vec<PathBuf> {
+        if self.paths.is_empty() {
+            vec![PathBuf::from(".")]
+        } else {
+            self.paths.clone()
+        }
+    }
+}
+
+impl ListArgs {
+    /// The format asked for on the command line, as for `scan`.
+    pub fn requested_format(&self) -> Option<OutputFormat> {
+        if let Some(format) = self.format {
+            Some(format)
+        } else if self.github {
+            Some(OutputFormat::Github)
+        } else if self.sarif {
+            Some(OutputFormat::Sarif)
+        } else if self.csv {
+            Some(OutputFormat::Csv)
+        } else if self.ndjson {
+            Some(OutputFormat::Ndjson)
+        } else if self.codequality {
+            Some(OutputFormat::Codequality)
+        } else if self.junit {
+            Some(OutputFormat::Junit)
+        } else if self.json {
+            Some(OutputFormat::Json)
+        } else {
+            None
+        }
+    }
+
+    /// The paths to scan, defaulting to the current directory.
+    pub fn scan_paths(&self) -> Vec<PathBuf> {
+        if self.paths.is_empty() {
+            vec![PathBuf::from(".")]
+        } else {
+            self.paths.clone()
+        }
     }
 }
 
 impl From<ScanArgs> for ListArgs {
     fn from(scan: ScanArgs) -> Self {
         Self {
-            path: scan.path,
+            paths: scan.paths,
             tags: scan.tags,
             include: scan.include,
             exclude: scan.exclude,
             json: scan.json,
-            filter: None,
+            github: scan.github,
+            sarif: scan.sarif,
+            csv: scan.csv,
+            ndjson: scan.ndjson,
+            codequality: scan.codequality,
+            junit: scan.junit,
+            format: scan.format,
+            filter: scan.filter,
+            min_priority: scan.min_priority,
+            author: scan.author,
+            overdue: scan.overdue,
+            show_resolved: scan.show_resolved,
             ignore_case: scan.ignore_case,
             no_require_colon: scan.no_require_colon,
+            allow_empty: scan.allow_empty,
+            match_anywhere: scan.match_anywhere,
+            skip_urls: scan.skip_urls,
+            multiline: scan.multiline,
+            lex_comments: scan.lex_comments,
+            inline_priority: scan.inline_priority,
+            print_matched_tags: scan.print_matched_tags,
+            relative_paths_posix: scan.relative_paths_posix,
+            with_description: scan.with_description,
+            tab_width: scan.tab_width,
+            context: scan.context,
         }
     }
 }
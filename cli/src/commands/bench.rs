@@ -0,0 +1,128 @@
+use super::load_config;
+use crate::config::Config;
+use crate::parser::TodoParser;
+use anyhow::{Context, Result};
+use std::hint::black_box;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Keep parsing at least this long so small files still give stable numbers.
+const MIN_DURATION: Duration = Duration::from_millis(500);
+
+/// Result of parsing the same content repeatedly.
+#[derive(Debug, Clone, Copy)]
+pub struct Throughput {
+    pub bytes: usize,
+    pub items: usize,
+    pub iterations: u32,
+    pub elapsed: Duration,
+}
+
+impl Throughput {
+    pub fn megabytes_per_second(&self) -> f64 {
+        (self.bytes as f64 * self.iterations as f64) / 1_000_000.0 / self.elapsed.as_secs_f64()
+    }
+
+    pub fn items_per_second(&self) -> f64 {
+        (self.items as f64 * self.iterations as f64) / self.elapsed.as_secs_f64()
+    }
+}
+
+/// Parse `content` over and over until `min_duration` has passed.
+pub fn measure(parser: &TodoParser, content: &str, min_duration: Duration) -> Throughput {
+    let start = Instant::now();
+    let mut iterations = 0;
+
+    loop {
+        let items = black_box(parser.parse_content(black_box(content))).len();
+        iterations += 1;
+        let elapsed = start.elapsed();
+        if elapsed >= min_duration {
+            return Throughput {
+                bytes: content.len(),
+                items,
+                iterations,
+                elapsed,
+            };
+        }
+    }
+}
+
+/// Report how fast `file` parses with the configured tags, for `--bench-parse`.
+pub fn run(file: &Path, config_path: Option<&Path>) -> Result<()> {
+    let config = load_config(&std::env::current_dir()?, config_path)?;
+    bench_file(&mut io::stdout(), file, &config, MIN_DURATION)
+}
+
+/// Parse `file` with `config`'s tags for at least `min_duration` and write
+/// the throughput to `writer`.
+fn bench_file<W: Write>(
+    writer: &mut W,
+    file: &Path,
+    config: &Config,
+    min_duration: Duration,
+) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let parser = config.build_parser()?;
+
+    let throughput = measure(&parser, &content, min_duration);
+    writeln!(
+        writer,
+        "{}: {} bytes, {} items, {} iterations in {:.2?}",
+        file.display(),
+        throughput.bytes,
+        throughput.items,
+        throughput.iterations,
+        throughput.elapsed
+    )?;
+    writeln!(
+        writer,
+        "{:.1} MB/s, {:.0} items/s",
+        throughput.megabytes_per_second(),
+        throughput.items_per_second()
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn bench_command_runs_on_a_file() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let file = std::env::temp_dir().join(format!("todo_bench_{unique}.rs"));
+        std::fs::write(&file, "// TODO: one\nfn main() {}\n// FIXME: two\n").unwrap();
+
+        let mut out = Vec::new();
+        let ran = bench_file(&mut out, &file, &Config::new(), Duration::ZERO);
+        let _ = std::fs::remove_file(&file);
+        ran.unwrap();
+        let report = String::from_utf8(out).unwrap();
+        assert!(report.contains("2 items, 1 iterations"), "{}", report);
+
+        let parser = TodoParser::new(&["TODO".to_string()], true);
+        let throughput = measure(&parser, "// TODO: a\n// TODO: b\n", Duration::ZERO);
+        assert_eq!(throughput.iterations, 1);
+        assert_eq!(throughput.items, 2);
+        assert!(throughput.megabytes_per_second() > 0.0);
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        let ran = bench_file(
+            &mut Vec::new(),
+            Path::new("/nonexistent/bench.rs"),
+            &Config::new(),
+            Duration::ZERO,
+        );
+        assert!(ran.is_err());
+    }
+}
@@ -26,8 +26,7 @@ pub fn run(args: cli::InitArgs) -> Result<()> {
     println!("  - tags: List of tags to search for");
     println!("  - include: File patterns to include");
     println!("  - exclude: File patterns to exclude");
-    println!("  - json: Default to JSON output");
-    println!("  - flat: Default to flat output");
+    println!("  - format: Default output format (tree, flat, json, ...)");
 
     Ok(())
 }
@@ -1,73 +1,104 @@
-use super::load_config;
+use super::{check_git_write, filter_by_tag_glob, load_config, run_pre_scan_hook};
 use crate::{
     cli,
-    parser::TodoParser,
-    printer::{OutputFormat, PrintOptions, Printer},
-    scanner::{ScanOptions, Scanner},
+    parser::{ParserOptions, TodoParser},
+    printer::{OutputFormat, PrintOptions, Printer, debug::print_matched_tags},
+    resolved,
+    scanner::Scanner,
 };
-use anyhow::{Context, Result};
-use std::path::PathBuf;
+use anyhow::Result;
+use chrono::Local;
+use todo_tree_core::SortKey;
 
 pub fn run(args: cli::ListArgs, global: &cli::GlobalOptions) -> Result<()> {
-    let path = args.path.clone().unwrap_or_else(|| PathBuf::from("."));
-    let path = path
-        .canonicalize()
-        .with_context(|| format!("Failed to resolve path: {}", path.display()))?;
+    let paths = args.scan_paths();
+    let path = Scanner::common_root(&paths, false)?;
 
     let mut config = load_config(&path, global.config.as_deref())?;
     config.merge_with_cli(crate::config::CliOptions {
         tags: args.tags.clone(),
         include: args.include.clone(),
         exclude: args.exclude.clone(),
-        json: args.json,
-        flat: true,
+        format: args.requested_format(),
+        github: args.github,
         no_color: global.no_color,
         ignore_case: args.ignore_case,
         no_require_colon: args.no_require_colon,
+        allow_empty: args.allow_empty,
+        match_anywhere: args.match_anywhere,
+        skip_urls: args.skip_urls,
+        multiline: args.multiline,
+        author: args.author.clone(),
     });
 
-    let case_sensitive = !args.ignore_case && !config.ignore_case;
-    let require_colon = if args.no_require_colon {
-        false
-    } else {
-        config.require_colon
-    };
+    let parser = TodoParser::from_options(
+        &config.scan_tags(),
+        ParserOptions {
+            lex_comments: args.lex_comments,
+            inline_priority: args.inline_priority,
+            tab_width: args.tab_width,
+            context_lines: args.context,
+            ..config.parser_options()
+        },
+    )?;
 
-    let parser = TodoParser::with_options(
-        &config.tags,
-        case_sensitive,
-        require_colon,
-        config.custom_pattern.as_deref(),
-    );
+    run_pre_scan_hook(&config, &path, global)?;
 
-    let scan_options = ScanOptions {
-        include: config.include.clone(),
-        exclude: config.exclude.clone(),
-        ..Default::default()
-    };
+    let scanner = Scanner::new(parser, config.to_scan_options());
+    let mut result = scanner.scan_many(&paths)?;
 
-    let scanner = Scanner::new(parser, scan_options);
-    let mut result = scanner.scan(&path)?;
+    let unfiltered = args.print_matched_tags.then(|| result.clone());
 
     if let Some(filter_tag) = &args.filter {
-        result = result.filter_by_tag(filter_tag);
+        result = filter_by_tag_glob(&result, filter_tag)?;
+    }
+
+    if let Some(author) = &config.author {
+        result = result.filter_by_author(author);
+    }
+
+    if let Some(min_priority) = args.min_priority {
+        result = result.filter_by_min_priority(min_priority);
+    }
+
+    if args.overdue {
+        result = result.overdue(Local::now().date_naive());
+    }
+
+    let format = config.output_format().unwrap_or(OutputFormat::Flat);
+    check_git_write(format, global)?;
+    if format == OutputFormat::Patch {
+        result = resolved::patch_candidates(&result, &path)?;
+    } else if !args.show_resolved {
+        let resolved = resolved::load_resolved(&path)?;
+        if !resolved.is_empty() {
+            result = result.without_ids(&resolved);
+        }
     }
 
     let print_options = PrintOptions {
-        format: if args.json {
-            OutputFormat::Json
-        } else {
-            OutputFormat::Flat
-        },
-        colored: !global.no_color,
+        format,
+        colored: !config.no_color,
         show_line_numbers: true,
         full_paths: false,
-        clickable_links: !global.no_color,
+        clickable_links: !config.no_color,
         base_path: Some(path),
-        show_summary: !args.json,
+        show_summary: format.is_human_readable(),
         group_by_tag: false,
+        head: None,
+        limit: None,
+        tail: None,
+        sort: SortKey::Path,
+        show_counts: false,
+        posix_paths: args.relative_paths_posix,
+        descriptions: args.with_description.then(|| config.tag_registry()),
+        case_report: false,
     };
 
+    if let Some(unfiltered) = &unfiltered {
+        print_matched_tags(&mut std::io::stderr(), unfiltered, &print_options)?;
+    }
+
     let printer = Printer::new(print_options);
     printer.print(&result)?;
 
@@ -1,8 +1,11 @@
-use crate::{cli, config::Config};
-use anyhow::Result;
+use crate::{cli, config::Config, hooks, printer::OutputFormat};
+use anyhow::{Context, Result, bail};
+use std::fs::File;
+use std::io::{BufWriter, IsTerminal, Write};
 use std::path::Path;
 use todo_tree_core::ScanResult;
 
+pub mod bench;
 pub mod init;
 pub mod list;
 pub mod scan;
@@ -11,13 +14,73 @@ pub mod tags;
 pub mod workflow;
 
 pub(crate) fn load_config(path: &Path, config_path: Option<&Path>) -> Result<Config> {
-    if let Some(config_path) = config_path {
-        return Config::load_from_file(config_path);
+    let mut config = match config_path {
+        Some(config_path) => Config::load_from_file(config_path)?,
+        None => Config::load(path)?.unwrap_or_else(Config::new),
+    };
+    config.resolve_color(
+        std::env::var_os("NO_COLOR").as_deref(),
+        std::io::stdout().is_terminal(),
+    );
+    Ok(config)
+}
+
+pub(crate) fn run_pre_scan_hook(
+    config: &Config,
+    path: &Path,
+    global: &cli::GlobalOptions,
+) -> Result<()> {
+    let Some(command) = &config.pre_scan_command else {
+        return Ok(());
+    };
+
+    if !global.allow_hooks {
+        eprintln!(
+            "Warning: skipping pre_scan_command {:?}; pass --allow-hooks to run it",
+            command
+        );
+        return Ok(());
     }
 
-    match Config::load(path)? {
-        Some(config) => Ok(config),
-        None => Ok(Config::new()),
+    hooks::run_pre_scan(command, path)
+}
+
+/// Refuse formats that write to the repository unless `--allow-git-write` was given.
+pub(crate) fn check_git_write(format: OutputFormat, global: &cli::GlobalOptions) -> Result<()> {
+    if format == OutputFormat::GitNotes && !global.allow_git_write {
+        bail!("--format git-notes writes to the repository; pass --allow-git-write to allow it");
+    }
+    Ok(())
+}
+
+/// Open where a report goes: `file`, created along with any missing parent
+/// directories, or stdout.
+pub(crate) fn output_writer(file: Option<&Path>) -> Result<Box<dyn Write>> {
+    let Some(file) = file else {
+        return Ok(Box::new(std::io::stdout().lock()));
+    };
+
+    if let Some(parent) = file.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let created = File::create(file)
+        .with_context(|| format!("Failed to create output file: {}", file.display()))?;
+    Ok(Box::new(BufWriter::new(created)))
+}
+
+/// Write a report with `write` to [`output_writer`]`(file)` and flush it.
+pub(crate) fn write_output(
+    file: Option<&Path>,
+    write: impl FnOnce(&mut dyn Write) -> std::io::Result<()>,
+) -> Result<()> {
+    let mut out = output_writer(file)?;
+    let written = write(&mut out).and_then(|()| out.flush());
+    match file {
+        Some(file) => {
+            written.with_context(|| format!("Failed to write output file: {}", file.display()))
+        }
+        None => Ok(written?),
     }
 }
 
@@ -40,6 +103,18 @@ pub(crate) fn save_config(config: &Config) -> Result<()> {
     config.save(&path)
 }
 
+/// Keep items whose tag matches `pattern`, a glob such as `FIX*` or a plain
+/// tag name, ignoring case either way.
+pub(crate) fn filter_by_tag_glob(result: &ScanResult, pattern: &str) -> Result<ScanResult> {
+    let glob =
+        glob::Pattern::new(pattern).with_context(|| format!("Invalid tag pattern: {}", pattern))?;
+    let options = glob::MatchOptions {
+        case_sensitive: false,
+        ..Default::default()
+    };
+    Ok(result.filter_items(|_, item| glob.matches_with(&item.tag, options)))
+}
+
 pub(crate) fn sort_results(result: &mut ScanResult, sort: cli::SortOrder) {
     match sort {
         cli::SortOrder::File => {}
@@ -53,5 +128,49 @@ pub(crate) fn sort_results(result: &mut ScanResult, sort: cli::SortOrder) {
                 items.sort_by_key(|item| std::cmp::Reverse(item.priority));
             }
         }
+        cli::SortOrder::Tag => {
+            for items in result.files_map.values_mut() {
+                items.sort_by(|a, b| a.tag.cmp(&b.tag));
+            }
+        }
+        cli::SortOrder::Age => {
+            for items in result.files_map.values_mut() {
+                items.sort_by_key(|item| item.age_key());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use todo_tree_core::TodoItem;
+
+    #[test]
+    fn tag_filter_accepts_globs() {
+        let item = |tag: &str| TodoItem {
+            tag: tag.to_string(),
+            line: 1,
+            ..Default::default()
+        };
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(
+            PathBuf::from("/repo/a.rs"),
+            vec![item("FIXME"), item("TODO"), item("FIX")],
+        );
+
+        let tags = |pattern: &str| -> Vec<String> {
+            filter_by_tag_glob(&result, pattern)
+                .unwrap()
+                .all_items()
+                .into_iter()
+                .map(|(_, item)| item.tag)
+                .collect()
+        };
+        assert_eq!(tags("FIX*"), vec!["FIXME", "FIX"]);
+        assert_eq!(tags("fix*"), vec!["FIXME", "FIX"]);
+        assert_eq!(tags("todo"), vec!["TODO"]);
+        assert!(filter_by_tag_glob(&result, "[").is_err());
     }
 }
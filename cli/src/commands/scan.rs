@@ -1,80 +1,235 @@
-use super::{load_config, sort_results};
+use super::{
+    check_git_write, filter_by_tag_glob, load_config, run_pre_scan_hook, sort_results, write_output,
+};
 use crate::{
-    cli,
-    config::CliOptions,
-    parser::TodoParser,
-    printer::{OutputFormat, PrintOptions, Printer},
-    scanner::{ScanOptions, Scanner},
+    blame, cli,
+    config::{CliOptions, Config},
+    parser::{ParserOptions, TodoParser},
+    printer::{
+        OutputFormat, PrintOptions, Printer, debug::print_matched_tags, summary::print_count,
+    },
+    resolved,
+    scanner::{ScanFilter, ScanOptions, Scanner},
+    thresholds::Thresholds,
+    watch,
 };
-use anyhow::{Context, Result};
-use std::path::PathBuf;
+use anyhow::{Result, bail};
+use chrono::Local;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn run(args: cli::ScanArgs, global: &cli::GlobalOptions) -> Result<()> {
-    let path = args.path.clone().unwrap_or_else(|| PathBuf::from("."));
-    let path = path
-        .canonicalize()
-        .with_context(|| format!("Failed to resolve path: {}", path.display()))?;
+    if !args.watch {
+        return scan_once(&args, global);
+    }
+
+    report(scan_once(&args, global));
+    let paths = args.scan_paths();
+    let path = Scanner::common_root(&paths, args.keep_link_paths)?;
+    let options = scan_options(&args, &merged_config(&args, global, &path)?);
+    let filter = ScanFilter::new(&paths, &options)?;
+    let (_watcher, events) = watch::watch(filter.roots())?;
+    watch::run_debounced(
+        &events,
+        watch::DEBOUNCE,
+        |path| filter.matches(path),
+        || {
+            if std::io::stdout().is_terminal() {
+                print!("{}", watch::CLEAR_SCREEN);
+            }
+            report(scan_once(&args, global));
+            Ok(())
+        },
+    )
+}
 
-    let mut config = load_config(&path, global.config.as_deref())?;
+/// Print a failed rescan and keep watching, as `main` would print it before exiting.
+fn report(result: Result<()>) {
+    if let Err(err) = result {
+        eprintln!("Error: {:#}", err);
+    }
+}
+
+fn merged_config(args: &cli::ScanArgs, global: &cli::GlobalOptions, path: &Path) -> Result<Config> {
+    let mut config = load_config(path, global.config.as_deref())?;
     config.merge_with_cli(CliOptions {
         tags: args.tags.clone(),
         include: args.include.clone(),
         exclude: args.exclude.clone(),
-        json: args.json,
-        flat: args.flat,
+        format: args
+            .requested_format()
+            .or_else(|| args.output.as_deref().and_then(OutputFormat::from_path)),
+        github: args.github,
         no_color: global.no_color,
         ignore_case: args.ignore_case,
         no_require_colon: args.no_require_colon,
+        allow_empty: args.allow_empty,
+        match_anywhere: args.match_anywhere,
+        skip_urls: args.skip_urls,
+        multiline: args.multiline,
+        author: args.author.clone(),
     });
+    Ok(config)
+}
 
-    let case_sensitive = !args.ignore_case && !config.ignore_case;
-    let require_colon = if args.no_require_colon {
-        false
-    } else {
-        config.require_colon
-    };
-
-    let parser = TodoParser::with_options(
-        &config.tags,
-        case_sensitive,
-        require_colon,
-        config.custom_pattern.as_deref(),
-    );
-
-    let scan_options = ScanOptions {
-        include: config.include.clone(),
-        exclude: config.exclude.clone(),
+fn scan_options(args: &cli::ScanArgs, config: &Config) -> ScanOptions {
+    ScanOptions {
         max_depth: args.depth,
         follow_links: args.follow_links,
         hidden: args.hidden,
-        threads: 0,
-        respect_gitignore: true,
+        respect_todoignore: !args.no_todoignore,
+        extensions: args.extensions.clone().unwrap_or_default(),
+        warn_unreadable: args.warn_unreadable,
+        keep_link_paths: args.keep_link_paths,
+        max_results: args.max_total_results,
+        ..config.to_scan_options()
+    }
+}
+
+fn scan_once(args: &cli::ScanArgs, global: &cli::GlobalOptions) -> Result<()> {
+    let paths = args.scan_paths();
+    let path = Scanner::common_root(&paths, args.keep_link_paths)?;
+    let config = merged_config(args, global, &path)?;
+
+    let parser = TodoParser::from_options(
+        &config.scan_tags(),
+        ParserOptions {
+            case_sensitive: !config.ignore_case && !args.case_report,
+            lex_comments: args.lex_comments,
+            inline_priority: args.inline_priority,
+            tab_width: args.tab_width,
+            context_lines: args.context,
+            ..config.parser_options()
+        },
+    )?;
+
+    let scanner = Scanner::new(parser, scan_options(args, &config));
+    if args.dry_run {
+        let files = scanner.list_files(&paths)?;
+        return write_output(args.output.as_deref(), |out| {
+            for file in &files {
+                writeln!(
+                    out,
+                    "{}",
+                    file.strip_prefix(&path).unwrap_or(file).display()
+                )?;
+            }
+            Ok(())
+        });
+    }
+
+    run_pre_scan_hook(&config, &path, global)?;
+
+    let mut result = match (&args.cache, paths.as_slice()) {
+        (None, _) => scanner.scan_many(&paths)?,
+        (Some(cache), [dir]) if dir.is_dir() => scanner.scan_cached(dir, &path.join(cache))?,
+        (Some(_), _) => bail!("--cache needs a single directory to scan"),
     };
+    for error in &result.errors {
+        eprintln!("Warning: skipped {}", error);
+    }
+
+    let unfiltered = args.print_matched_tags.then(|| result.clone());
+
+    if let Some(filter_tag) = &args.filter {
+        result = filter_by_tag_glob(&result, filter_tag)?;
+    }
+
+    if let Some(author) = &config.author {
+        result = result.filter_by_author(author);
+    }
+
+    if let Some(min_priority) = args.min_priority {
+        result = result.filter_by_min_priority(min_priority);
+    }
+
+    if args.overdue {
+        result = result.overdue(Local::now().date_naive());
+    }
+
+    let format = config.output_format().unwrap_or(OutputFormat::Tree);
+    check_git_write(format, global)?;
+    if format == OutputFormat::Patch {
+        result = resolved::patch_candidates(&result, &path)?;
+    } else if !args.show_resolved {
+        let resolved = resolved::load_resolved(&path)?;
+        if !resolved.is_empty() {
+            result = result.without_ids(&resolved);
+        }
+    }
 
-    let scanner = Scanner::new(parser, scan_options);
-    let mut result = scanner.scan(&path)?;
+    if args.dedup {
+        result = result.deduplicated();
+    }
+
+    if let Some(count) = args.sample {
+        let seed = args.seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or_default()
+        });
+        result = result.sample(count, seed);
+    }
+
+    if args.blame || args.sort == cli::SortOrder::Age {
+        blame::annotate(&mut result);
+    }
 
     sort_results(&mut result, args.sort);
 
+    if args.interactive_resolve {
+        let stdin = std::io::stdin();
+        let ids =
+            resolved::prompt_resolve(&result, &path, &mut stdin.lock(), &mut std::io::stdout())?;
+        resolved::append_resolved(&path, &ids)?;
+        println!(
+            "Marked {} item(s) resolved in {}",
+            ids.len(),
+            resolved::RESOLVED_FILE
+        );
+        return Ok(());
+    }
+
+    let to_terminal = args.output.is_none();
     let print_options = PrintOptions {
-        format: if args.json {
-            OutputFormat::Json
-        } else if args.flat {
-            OutputFormat::Flat
-        } else {
-            OutputFormat::Tree
-        },
-        colored: !global.no_color,
+        format,
+        colored: !config.no_color && to_terminal,
         show_line_numbers: true,
         full_paths: false,
-        clickable_links: !global.no_color,
+        clickable_links: !config.no_color && to_terminal,
         base_path: Some(path),
-        show_summary: !args.json,
+        show_summary: format.is_human_readable(),
         group_by_tag: args.group_by_tag,
+        head: args.head,
+        tail: args.tail,
+        limit: args.limit,
+        sort: args.sort.into(),
+        show_counts: args.show_counts,
+        posix_paths: args.relative_paths_posix,
+        descriptions: args.with_description.then(|| config.tag_registry()),
+        case_report: args.case_report,
     };
 
-    let printer = Printer::new(print_options);
-    printer.print(&result)?;
+    if let Some(unfiltered) = &unfiltered {
+        print_matched_tags(&mut std::io::stderr(), unfiltered, &print_options)?;
+    }
+
+    write_output(args.output.as_deref(), |mut out| {
+        if args.count {
+            print_count(&mut out, &result.summary)
+        } else if args.fingerprint {
+            writeln!(out, "{}", result.fingerprint())
+        } else {
+            Printer::new(print_options).print_to(&mut out, &result)
+        }
+    })?;
 
-    Ok(())
+    Thresholds {
+        max_total: args.max_total,
+        max_tags: args.max_tag.clone(),
+        fail_on_found: args.fail_on_found,
+    }
+    .check(&result.summary)
 }
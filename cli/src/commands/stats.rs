@@ -20,7 +20,7 @@ pub fn run(args: cli::StatsArgs, global: &cli::GlobalOptions) -> Result<()> {
         .with_context(|| format!("Failed to resolve path: {}", path.display()))?;
 
     let config = load_config(&path, global.config.as_deref())?;
-    let tags = args.tags.clone().unwrap_or(config.tags.clone());
+    let tags = args.tags.clone().unwrap_or_else(|| config.scan_tags());
 
     let parser = TodoParser::new(&tags, false);
     let scanner = Scanner::new(parser, ScanOptions::default());
@@ -68,7 +68,7 @@ pub fn run(args: cli::StatsArgs, global: &cli::GlobalOptions) -> Result<()> {
             let filled = ((percentage / 100.0) * bar_width as f64) as usize;
             let bar: String = "█".repeat(filled) + &"░".repeat(bar_width - filled);
 
-            if global.no_color {
+            if global.no_color || config.no_color {
                 println!("  {:<8} {:>4} ({:>5.1}%) {}", tag, count, percentage, bar);
             } else {
                 let color = priority_to_color(Priority::from_tag(tag));
@@ -1,7 +1,6 @@
 use super::load_config;
 use crate::{cli, utils::display::priority_to_color};
 use anyhow::Result;
-use todo_tree_core::Priority;
 use todo_tree_core::tags::default_tag_names;
 
 pub fn run(args: cli::TagsArgs, global: &cli::GlobalOptions) -> Result<()> {
@@ -47,11 +46,11 @@ pub fn run(args: cli::TagsArgs, global: &cli::GlobalOptions) -> Result<()> {
     } else {
         use colored::Colorize;
         println!("{}", "Configured tags:".bold());
-        for tag in &config.tags {
-            if global.no_color {
+        for tag in &config.scan_tags() {
+            if global.no_color || config.no_color {
                 println!("  - {}", tag);
             } else {
-                let color = priority_to_color(Priority::from_tag(tag));
+                let color = priority_to_color(config.priority_for(tag));
                 println!("  - {}", tag.color(color));
             }
         }
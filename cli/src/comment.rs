@@ -0,0 +1,191 @@
+/// Comment markers and string delimiters for a family of languages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommentSyntax {
+    /// Markers that start a comment running to the end of the line.
+    pub line: &'static [&'static str],
+    /// Opening and closing markers of a block comment.
+    pub block: Option<(&'static str, &'static str)>,
+    /// Characters that open and close string literals.
+    pub quotes: &'static [char],
+    /// Whether `'` encloses one-character literals such as `'"'`. A `'` that
+    /// does not, like a Rust lifetime, opens nothing.
+    pub char_literals: bool,
+}
+
+impl CommentSyntax {
+    /// `//` and `/* */`: C, C++, Java, JavaScript, Rust, Go, ...
+    pub const C_STYLE: Self = Self {
+        line: &["//"],
+        block: Some(("/*", "*/")),
+        quotes: &['"', '`'],
+        char_literals: true,
+    };
+
+    /// `#`: shell, Ruby, YAML, TOML, ... `'` is not a quote, since these
+    /// files are full of apostrophes in unquoted text (`it's fine # TODO`).
+    pub const HASH: Self = Self {
+        line: &["#"],
+        block: None,
+        quotes: &['"'],
+        char_literals: false,
+    };
+
+    /// `#` plus docstrings, which commonly carry TODOs in Python.
+    pub const PYTHON: Self = Self {
+        line: &["#"],
+        block: Some(("\"\"\"", "\"\"\"")),
+        quotes: &['"', '\''],
+        char_literals: false,
+    };
+
+    /// `<!-- -->`: HTML, XML, Markdown.
+    pub const MARKUP: Self = Self {
+        line: &[],
+        block: Some(("<!--", "-->")),
+        quotes: &[],
+        char_literals: false,
+    };
+
+    /// `--`: SQL, Lua, Haskell.
+    pub const DOUBLE_DASH: Self = Self {
+        line: &["--"],
+        block: None,
+        quotes: &['\''],
+        char_literals: false,
+    };
+
+    /// `;`: Lisp, Clojure, assembly, INI.
+    pub const SEMICOLON: Self = Self {
+        line: &[";"],
+        block: None,
+        quotes: &['"'],
+        char_literals: false,
+    };
+
+    /// Byte offset of the first comment on `line`, skipping markers that sit
+    /// inside string literals.
+    ///
+    /// Lines starting with `*` are treated as block comment continuations for
+    /// syntaxes with `/* */` blocks.
+    pub fn comment_start(&self, line: &str) -> Option<usize> {
+        if self.block.is_some_and(|(open, _)| open == "/*") {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('*') && !trimmed.starts_with("*/") {
+                return Some(line.len() - trimmed.len());
+            }
+        }
+
+        self.marker_start(line)
+    }
+
+    /// Byte offset of the first comment marker outside a string literal,
+    /// without the `*` continuation heuristic.
+    pub fn marker_start(&self, line: &str) -> Option<usize> {
+        let mut in_string: Option<char> = None;
+        let mut skip_to = 0;
+        let mut chars = line.char_indices();
+
+        while let Some((idx, c)) = chars.next() {
+            if idx < skip_to {
+                continue;
+            }
+            if let Some(quote) = in_string {
+                if c == '\\' {
+                    chars.next();
+                } else if c == quote {
+                    in_string = None;
+                }
+                continue;
+            }
+
+            let rest = &line[idx..];
+            if self.line.iter().any(|marker| rest.starts_with(marker))
+                || self.block.is_some_and(|(open, _)| rest.starts_with(open))
+            {
+                return Some(idx);
+            }
+
+            if self.quotes.contains(&c) {
+                in_string = Some(c);
+            } else if c == '\'' && self.char_literals {
+                skip_to = idx + char_literal_len(rest);
+            }
+        }
+
+        None
+    }
+
+    /// Offset just past the marker closing a block comment that is open at
+    /// the start of `line`, or `None` if the block runs past the line.
+    pub fn block_end(&self, line: &str) -> Option<usize> {
+        let (_, close) = self.block?;
+        line.find(close).map(|idx| idx + close.len())
+    }
+
+    /// Whether a block comment is still open at the end of `line`, given
+    /// whether one was open at its start.
+    pub fn ends_in_block(&self, line: &str, in_block: bool) -> bool {
+        let Some((open, close)) = self.block else {
+            return false;
+        };
+
+        let mut rest = line;
+        if in_block {
+            match self.block_end(rest) {
+                Some(end) => rest = &rest[end..],
+                None => return true,
+            }
+        }
+
+        while let Some(start) = self.marker_start(rest) {
+            let Some(body) = rest[start..].strip_prefix(open) else {
+                // A line comment runs to the end of the line.
+                return false;
+            };
+            match body.find(close) {
+                Some(idx) => rest = &body[idx + close.len()..],
+                None => return true,
+            }
+        }
+
+        false
+    }
+}
+
+/// Length of the character literal at the start of `text`, e.g. `'"'` or
+/// `'\n'`, or 0 if the `'` there does not open one.
+fn char_literal_len(text: &str) -> usize {
+    let body = &text[1..];
+    let end = if body.starts_with('\\') {
+        body.char_indices()
+            .skip(2)
+            .take(10)
+            .find(|&(_, c)| c == '\'')
+            .map(|(idx, _)| idx)
+    } else {
+        body.char_indices()
+            .nth(1)
+            .filter(|&(_, c)| c == '\'')
+            .map(|(idx, _)| idx)
+    };
+    end.map_or(0, |idx| idx + 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_block_comments_across_lines() {
+        let c = CommentSyntax::C_STYLE;
+
+        assert!(c.ends_in_block("let x = 1; /* opens", false));
+        assert!(!c.ends_in_block("/* opens */ and closes", false));
+        assert!(!c.ends_in_block("let s = \"/* not a comment\";", false));
+        assert!(!c.ends_in_block("// /* inside a line comment", false));
+        assert!(c.ends_in_block("   still inside", true));
+        assert!(!c.ends_in_block("   ends */ code();", true));
+        assert!(c.ends_in_block("   ends */ code(); /* again", true));
+        assert_eq!(c.block_end("  done */ x"), Some(9));
+    }
+}
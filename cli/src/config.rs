@@ -1,47 +1,139 @@
+use crate::parser::{ParserOptions, TodoParser};
+use crate::printer::OutputFormat;
+use crate::scanner::ScanOptions;
 use anyhow::{Context, Result};
 use directories_next::BaseDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ffi::OsStr;
 use std::path::Path;
 use todo_tree_core::tags::default_tag_names;
+use todo_tree_core::{CustomTag, Priority, TagRegistry};
+
+const LOCAL_CONFIG_FILES: [&str; 4] = [".todorc", ".todorc.json", ".todorc.yaml", ".todorc.yml"];
 
 #[derive(Debug, Clone, Default)]
 pub struct CliOptions {
     pub tags: Option<Vec<String>>,
     pub include: Option<Vec<String>>,
     pub exclude: Option<Vec<String>>,
-    pub json: bool,
-    pub flat: bool,
+    /// Format picked on the command line, if any; see `ScanArgs::requested_format`.
+    pub format: Option<OutputFormat>,
+    pub github: bool,
     pub no_color: bool,
     pub ignore_case: bool,
     pub no_require_colon: bool,
+    pub allow_empty: bool,
+    pub match_anywhere: bool,
+    pub skip_urls: bool,
+    pub multiline: bool,
+    pub author: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// Fields missing from a config file take their value from [`Config::new`],
+/// so a partial file still searches the default tags.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
+    /// Marks the directory holding this config as the project root, like
+    /// editorconfig's `root = true`. Configs above it are not merged in.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub root: bool,
     pub tags: Vec<String>,
     pub include: Vec<String>,
     pub exclude: Vec<String>,
-    pub json: bool,
-    pub flat: bool,
+    /// Default output format, used when none is given on the command line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<OutputFormat>,
+    /// `json: true` from configs written before `format` existed. Read but
+    /// never written; see [`Config::output_format`].
+    #[serde(skip_serializing)]
+    json: bool,
+    /// `flat: true` from configs written before `format` existed.
+    #[serde(skip_serializing)]
+    flat: bool,
+    /// Default to GitHub Actions workflow annotations.
+    pub github: bool,
     pub no_color: bool,
     pub custom_pattern: Option<String>,
+    /// Also match tags in other cases (`todo:`, `Todo:`). Off by default, so
+    /// only tags written exactly as configured are found.
     pub ignore_case: bool,
     pub require_colon: bool,
+    /// Report tags with no message after them, e.g. a bare `// TODO`.
+    pub allow_empty: bool,
+    /// Report tags outside comments too, even for languages with known comment syntax.
+    pub match_anywhere: bool,
+    /// Skip tags inside a URL, such as the fragment of `https://example.com/#TODO`.
+    pub skip_urls: bool,
+    /// Include `*` continuation lines of block comments in each item's body.
+    pub multiline: bool,
+    /// Only report items assigned to this author.
+    pub author: Option<String>,
+    /// Priority overrides by tag, e.g. `{"SECURITY": "critical"}`. Tags not
+    /// listed keep their built-in priority.
+    pub tag_priorities: HashMap<String, Priority>,
+    /// Extra tags, or replacements for built-in ones, with a description and priority.
+    /// Their names are searched in addition to `tags`.
+    pub tag_definitions: Vec<CustomTag>,
+    /// Shell command run in the scan root before walking. Only executed with `--allow-hooks`.
+    pub pre_scan_command: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Config {
+    /// Turn colors off when `NO_COLOR` is set to a non-empty value or stdout
+    /// is not a terminal. Never turns them back on, so `--no-color` and a
+    /// configured `no_color` always hold.
+    pub fn resolve_color(&mut self, no_color_env: Option<&OsStr>, stdout_is_terminal: bool) {
+        let env_disables = no_color_env.is_some_and(|value| !value.is_empty());
+        if env_disables || !stdout_is_terminal {
+            self.no_color = true;
+        }
+    }
+
+    /// The configured default format. An explicit `format` wins, then the
+    /// older booleans in the order `github`, `json`, `flat`, so a config
+    /// setting several of them always resolves the same way.
+    pub fn output_format(&self) -> Option<OutputFormat> {
+        self.format.or(if self.github {
+            Some(OutputFormat::Github)
+        } else if self.json {
+            Some(OutputFormat::Json)
+        } else if self.flat {
+            Some(OutputFormat::Flat)
+        } else {
+            None
+        })
+    }
+
     pub fn new() -> Self {
         Self {
+            root: false,
             tags: default_tag_names(),
             include: Vec::new(),
             exclude: Vec::new(),
+            format: None,
             json: false,
             flat: false,
+            github: false,
             no_color: false,
             custom_pattern: None,
             ignore_case: false,
             require_colon: true,
+            allow_empty: false,
+            match_anywhere: false,
+            skip_urls: false,
+            multiline: false,
+            author: None,
+            tag_priorities: HashMap::new(),
+            tag_definitions: Vec::new(),
+            pre_scan_command: None,
         }
     }
 
@@ -51,25 +143,11 @@ impl Config {
     /// 1. .todorc in the current directory
     /// 2. .todorc.json in the current directory
     /// 3. .todorc.yaml or .todorc.yml in the current directory
-    /// 4. ~/.config/todo-tree/config.json (global config)
+    /// 4. The same files in each parent directory, up to the project root;
+    ///    every config found on the way is merged, the nearest winning
+    /// 5. ~/.config/todo-tree/config.json (global config)
     pub fn load(start_path: &Path) -> Result<Option<Self>> {
-        let local_configs = [
-            start_path.join(".todorc"),
-            start_path.join(".todorc.json"),
-            start_path.join(".todorc.yaml"),
-            start_path.join(".todorc.yml"),
-        ];
-
-        for config_path in &local_configs {
-            if config_path.exists() {
-                return Self::load_from_file(config_path).map(Some);
-            }
-        }
-
-        if let Some(parent) = start_path.parent()
-            && parent != start_path
-            && let Ok(Some(config)) = Self::load(parent)
-        {
+        if let Some(config) = Self::load_local(start_path)? {
             return Ok(Some(config));
         }
 
@@ -91,7 +169,50 @@ impl Config {
         Ok(None)
     }
 
+    /// Merge the local configs found walking up from `start_path`, each
+    /// setting only the keys it names over those of its parents.
+    ///
+    /// Like editorconfig, the walk ends at the project root: a `root: true`
+    /// config or a directory containing `.git`, so unrelated configs further
+    /// up are never picked up.
+    fn load_local(start_path: &Path) -> Result<Option<Self>> {
+        let mut layers = Vec::new();
+        for dir in start_path.ancestors() {
+            if let Some(config_path) = LOCAL_CONFIG_FILES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|path| path.exists())
+            {
+                let value = Self::read_value(&config_path)?;
+                let is_root = Self::from_value(value.clone(), &config_path)?.root;
+                layers.push((config_path, value));
+                if is_root {
+                    break;
+                }
+            }
+
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+
+        let Some((nearest, _)) = layers.first() else {
+            return Ok(None);
+        };
+        let mut merged = serde_json::Value::Object(Default::default());
+        for (_, value) in layers.iter().rev() {
+            overlay(&mut merged, value);
+        }
+        Self::checked(Self::from_value(merged, nearest)?, nearest).map(Some)
+    }
+
     pub fn load_from_file(path: &Path) -> Result<Self> {
+        let config = Self::from_value(Self::read_value(path)?, path)?;
+        Self::checked(config, path)
+    }
+
+    /// The raw contents of a config file, before defaults are filled in.
+    fn read_value(path: &Path) -> Result<serde_json::Value> {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
 
@@ -101,14 +222,156 @@ impl Config {
         } else {
             serde_json::from_str(&content).or_else(|_| yaml_serde::from_str(&content))
         };
-
         parse_result.with_context(|| format!("Failed to parse config: {}", path.display()))
     }
 
+    fn from_value(value: serde_json::Value, path: &Path) -> Result<Self> {
+        serde_json::from_value(value)
+            .with_context(|| format!("Failed to parse config: {}", path.display()))
+    }
+
+    fn checked(config: Self, path: &Path) -> Result<Self> {
+        for conflict in config.conflicts() {
+            eprintln!("Warning: {}: {}", path.display(), conflict);
+        }
+        Ok(config)
+    }
+
+    /// Per-tag settings that contradict each other, e.g. one tag given two
+    /// different priorities. Which one wins is an accident of ordering, so
+    /// these are reported as warnings when the config is loaded.
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut overrides: BTreeMap<String, BTreeSet<Priority>> = BTreeMap::new();
+        for (tag, priority) in &self.tag_priorities {
+            overrides
+                .entry(tag.trim().to_uppercase())
+                .or_default()
+                .insert(*priority);
+        }
+
+        let mut definitions: BTreeMap<String, Vec<&CustomTag>> = BTreeMap::new();
+        for tag in &self.tag_definitions {
+            definitions
+                .entry(tag.name.trim().to_uppercase())
+                .or_default()
+                .push(tag);
+        }
+
+        let mut conflicts = Vec::new();
+        for (tag, priorities) in &overrides {
+            if priorities.len() > 1 {
+                conflicts.push(format!(
+                    "tag_priorities sets {} to {}",
+                    tag,
+                    join_priorities(priorities)
+                ));
+            }
+        }
+
+        for (tag, defs) in &definitions {
+            if defs.iter().any(|def| *def != defs[0]) {
+                conflicts.push(format!(
+                    "tag_definitions lists {} {} times with different settings",
+                    tag,
+                    defs.len()
+                ));
+            }
+
+            let defined: BTreeSet<Priority> = defs.iter().map(|def| def.priority).collect();
+            if let Some(priorities) = overrides.get(tag)
+                && priorities != &defined
+            {
+                conflicts.push(format!(
+                    "{} is {} in tag_definitions but {} in tag_priorities",
+                    tag,
+                    join_priorities(&defined),
+                    join_priorities(priorities)
+                ));
+            }
+        }
+
+        conflicts
+    }
+
+    /// Built-in tags merged with `tag_definitions`.
+    pub fn tag_registry(&self) -> TagRegistry {
+        TagRegistry::new(self.tag_definitions.iter().cloned())
+    }
+
+    /// `tags` plus any names from `tag_definitions` not already listed.
+    pub fn scan_tags(&self) -> Vec<String> {
+        let mut tags = self.tags.clone();
+        for tag in &self.tag_definitions {
+            if !tags
+                .iter()
+                .any(|t| t.trim().eq_ignore_ascii_case(tag.name.trim()))
+            {
+                tags.push(tag.name.clone());
+            }
+        }
+        tags
+    }
+
+    /// Parser settings from this config. Per-run options such as
+    /// `context_lines` keep their defaults for callers to set on top.
+    pub fn parser_options(&self) -> ParserOptions {
+        ParserOptions {
+            case_sensitive: !self.ignore_case,
+            require_colon: self.require_colon,
+            custom_regex: self.custom_pattern.clone(),
+            allow_empty: self.allow_empty,
+            comments_only: !self.match_anywhere,
+            skip_urls: self.skip_urls,
+            multiline: self.multiline,
+            tag_priorities: self.resolved_tag_priorities(),
+            ..Default::default()
+        }
+    }
+
+    /// A parser for [`Config::scan_tags`] with [`Config::parser_options`], as
+    /// the CLI builds it. Fails if `custom_pattern` is invalid.
+    pub fn build_parser(&self) -> Result<TodoParser> {
+        TodoParser::from_options(&self.scan_tags(), self.parser_options())
+    }
+
+    /// Walker settings from this config; the rest keep their defaults.
+    pub fn to_scan_options(&self) -> ScanOptions {
+        ScanOptions {
+            include: self.include.clone(),
+            exclude: self.exclude.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Priority overrides for the parser: `tag_definitions`, then `tag_priorities` on top.
+    pub fn resolved_tag_priorities(&self) -> HashMap<String, Priority> {
+        let mut priorities: HashMap<String, Priority> = self
+            .tag_definitions
+            .iter()
+            .map(|tag| (tag.name.to_uppercase(), tag.priority))
+            .collect();
+        for (tag, priority) in &self.tag_priorities {
+            priorities.insert(tag.to_uppercase(), *priority);
+        }
+        priorities
+    }
+
+    /// Priority of `tag`, honouring config overrides before the built-in mapping.
+    pub fn priority_for(&self, tag: &str) -> Priority {
+        self.resolved_tag_priorities()
+            .get(tag.trim().to_uppercase().as_str())
+            .copied()
+            .unwrap_or_else(|| Priority::from_tag(tag))
+    }
+
     pub fn merge_with_cli(&mut self, cli: CliOptions) {
         if let Some(tags) = cli.tags
             && !tags.is_empty()
         {
+            // Explicit tags replace the search set, so only keep definitions
+            // for tags that are still being searched.
+            self.tag_definitions
+                .retain(|def| tags.iter().any(|t| t.eq_ignore_ascii_case(&def.name)));
             self.tags = tags;
         }
 
@@ -124,11 +387,11 @@ impl Config {
             self.exclude.extend(exclude);
         }
 
-        if cli.json {
-            self.json = true;
+        if cli.format.is_some() {
+            self.format = cli.format;
         }
-        if cli.flat {
-            self.flat = true;
+        if cli.github {
+            self.github = true;
         }
         if cli.no_color {
             self.no_color = true;
@@ -141,6 +404,26 @@ impl Config {
         if cli.no_require_colon {
             self.require_colon = false;
         }
+
+        if cli.allow_empty {
+            self.allow_empty = true;
+        }
+
+        if cli.match_anywhere {
+            self.match_anywhere = true;
+        }
+
+        if cli.skip_urls {
+            self.skip_urls = true;
+        }
+
+        if cli.multiline {
+            self.multiline = true;
+        }
+
+        if cli.author.is_some() {
+            self.author = cli.author;
+        }
     }
 
     pub fn save(&self, path: &Path) -> Result<()> {
@@ -157,3 +440,275 @@ impl Config {
         Ok(())
     }
 }
+
+fn join_priorities(priorities: &BTreeSet<Priority>) -> String {
+    priorities
+        .iter()
+        .map(Priority::to_string)
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+/// Set every key of `layer` on `base`, merging nested maps key by key.
+fn overlay(base: &mut serde_json::Value, layer: &serde_json::Value) {
+    match (base, layer) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(layer)) => {
+            for (key, value) in layer {
+                match base.get_mut(key) {
+                    Some(existing) if existing.is_object() && value.is_object() => {
+                        overlay(existing, value)
+                    }
+                    _ => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, layer) => *base = layer.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_root(name: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("todo_config_{name}_{unique}"));
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn discovery_stops_at_root_config() {
+        let outer = temp_root("root_marker");
+        let project = outer.join("project");
+        let nested = project.join("src").join("deep");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            outer.join(".todorc.json"),
+            r#"{"tags": ["OUTER"], "exclude": ["vendor/**"]}"#,
+        )
+        .unwrap();
+        fs::write(project.join(".todorc.yaml"), "root: true\ntags: [INNER]\n").unwrap();
+
+        let rooted = Config::load_local(&nested).unwrap();
+        fs::write(project.join(".todorc.yaml"), "tags: [INNER]\n").unwrap();
+        let unrooted = Config::load_local(&nested).unwrap();
+        let _ = fs::remove_dir_all(&outer);
+
+        let rooted = rooted.expect("project config should be found");
+        assert!(rooted.root);
+        assert_eq!(rooted.tags, vec!["INNER"]);
+        assert!(rooted.exclude.is_empty());
+        assert_eq!(unrooted.unwrap().exclude, vec!["vendor/**"]);
+    }
+
+    #[test]
+    fn nested_configs_override_only_the_keys_they_set() {
+        let project = temp_root("layered");
+        let nested = project.join("services").join("api");
+        fs::create_dir_all(project.join(".git")).unwrap();
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            project.join(".todorc.json"),
+            r#"{"tags": ["TODO"], "exclude": ["vendor/**"], "tag_priorities": {"TODO": "low", "HACK": "high"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            nested.join(".todorc.yaml"),
+            "tags: [TODO, SECURITY]\ntag_priorities:\n  HACK: critical\n",
+        )
+        .unwrap();
+
+        let config = Config::load_local(&nested).unwrap().unwrap();
+        let bad_parent = fs::write(project.join(".todorc.json"), r#"{"tags": 42}"#)
+            .map(|()| Config::load_local(&nested));
+        let _ = fs::remove_dir_all(&project);
+
+        assert_eq!(config.tags, vec!["TODO", "SECURITY"]);
+        assert_eq!(config.exclude, vec!["vendor/**"]);
+        assert_eq!(config.tag_priorities.get("TODO"), Some(&Priority::Low));
+        assert_eq!(config.tag_priorities.get("HACK"), Some(&Priority::Critical));
+        let err = format!("{:#}", bad_parent.unwrap().unwrap_err());
+        assert!(err.contains(".todorc.json"), "{}", err);
+    }
+
+    #[test]
+    fn discovery_stops_at_git_directory() {
+        let outer = temp_root("git_marker");
+        let repo = outer.join("repo");
+        let nested = repo.join("src");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(outer.join(".todorc.json"), r#"{"tags": ["OUTER"]}"#).unwrap();
+
+        let config = Config::load_local(&nested).unwrap();
+        let _ = fs::remove_dir_all(&outer);
+
+        assert!(config.is_none());
+    }
+
+    #[test]
+    fn conflicting_priority_overrides_are_reported() {
+        let config: Config = serde_json::from_str(
+            r#"{
+                "tag_priorities": {"todo": "Low", "TODO": "High", "BUG": "Critical"},
+                "tag_definitions": [
+                    {"name": "REVIEW", "priority": "High"},
+                    {"name": "review", "priority": "Low"},
+                    {"name": "BUG", "priority": "Low"}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.conflicts(),
+            vec![
+                "tag_priorities sets TODO to Low and High",
+                "BUG is Low in tag_definitions but Critical in tag_priorities",
+                "tag_definitions lists REVIEW 2 times with different settings",
+            ]
+        );
+        assert!(Config::new().conflicts().is_empty());
+    }
+
+    #[test]
+    fn no_color_env_and_non_terminal_turn_colors_off() {
+        let resolved = |env: Option<&str>, terminal: bool, configured: bool| {
+            let mut config = Config {
+                no_color: configured,
+                ..Config::new()
+            };
+            config.resolve_color(env.map(OsStr::new), terminal);
+            config.no_color
+        };
+
+        assert!(resolved(Some("1"), true, false));
+        assert!(resolved(None, false, false));
+        assert!(resolved(None, true, true));
+        assert!(!resolved(None, true, false));
+        assert!(!resolved(Some(""), true, false));
+    }
+
+    #[test]
+    fn legacy_format_flags_resolve_deterministically() {
+        let load = |text: &str| serde_json::from_str::<Config>(text).unwrap();
+
+        assert_eq!(Config::new().output_format(), None);
+        assert_eq!(
+            load(r#"{"json": true, "flat": true}"#).output_format(),
+            Some(OutputFormat::Json)
+        );
+        assert_eq!(
+            load(r#"{"flat": true, "json": true}"#).output_format(),
+            Some(OutputFormat::Json)
+        );
+        assert_eq!(
+            load(r#"{"flat": true}"#).output_format(),
+            Some(OutputFormat::Flat)
+        );
+        assert_eq!(
+            load(r#"{"json": true, "format": "tree-json"}"#).output_format(),
+            Some(OutputFormat::TreeJson)
+        );
+
+        let mut config = load(r#"{"json": true}"#);
+        config.merge_with_cli(CliOptions {
+            format: Some(OutputFormat::Flat),
+            ..Default::default()
+        });
+        assert_eq!(config.output_format(), Some(OutputFormat::Flat));
+
+        let saved = serde_json::to_value(&config).unwrap();
+        assert_eq!(saved["format"], "flat");
+        assert!(saved.get("json").is_none());
+        assert!(saved.get("flat").is_none());
+    }
+
+    #[test]
+    fn conversions_carry_filters_tags_and_case() {
+        let mut config = Config {
+            tags: vec!["TODO".to_string(), "SECURITY".to_string()],
+            include: vec!["*.rs".to_string()],
+            exclude: vec!["target/**".to_string()],
+            ..Config::default()
+        };
+
+        let scan_options = config.to_scan_options();
+        assert_eq!(scan_options.include, vec!["*.rs"]);
+        assert_eq!(scan_options.exclude, vec!["target/**"]);
+        assert!(scan_options.respect_gitignore);
+
+        let parser = config.build_parser().unwrap();
+        assert!(parser.parse_line("// SECURITY: check", 1).is_some());
+        assert!(parser.parse_line("// FIXME: not listed", 1).is_none());
+        assert!(parser.parse_line("// todo: lowercase", 1).is_none());
+
+        config.ignore_case = true;
+        config.require_colon = false;
+        assert!(!config.parser_options().case_sensitive);
+        let parser = config.build_parser().unwrap();
+        let item = parser.parse_line("// todo lowercase", 1).unwrap();
+        assert_eq!(item.tag, "TODO");
+    }
+
+    #[test]
+    fn comments_only_is_the_default_and_match_anywhere_turns_it_off() {
+        let mut config = Config::new();
+        assert!(config.parser_options().comments_only);
+
+        config.merge_with_cli(CliOptions {
+            match_anywhere: true,
+            ..Default::default()
+        });
+        assert!(!config.parser_options().comments_only);
+
+        let config: Config = serde_json::from_str(r#"{"match_anywhere": true}"#).unwrap();
+        assert!(!config.parser_options().comments_only);
+    }
+
+    #[test]
+    fn default_parser_skips_tags_in_rust_strings() {
+        let root = temp_root("strings");
+        let path = root.join("lib.rs");
+        fs::write(
+            &path,
+            "let msg = \"TODO: not a real todo\";\n// TODO: real one\n",
+        )
+        .unwrap();
+
+        let items = Config::new().build_parser().unwrap().parse_file(&path);
+        let _ = fs::remove_dir_all(&root);
+
+        let items = items.unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].message, "real one");
+    }
+
+    #[test]
+    fn every_default_path_uses_the_core_tag_list() {
+        assert_eq!(Config::new().tags, default_tag_names());
+        assert_eq!(Config::default().tags, default_tag_names());
+
+        let root = temp_root("partial");
+        let path = root.join(".todorc.json");
+        fs::write(&path, r#"{"ignore_case": true}"#).unwrap();
+        let partial = Config::load_from_file(&path).unwrap();
+        fs::write(&path, r#"{"tags": []}"#).unwrap();
+        let emptied = Config::load_from_file(&path).unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(partial.tags, default_tag_names());
+        assert!(partial.require_colon);
+        assert!(partial.ignore_case);
+        assert!(emptied.tags.is_empty());
+    }
+}
@@ -0,0 +1,56 @@
+//! Process exit codes, so CI can tell a failed check from a broken run.
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0 | Success |
+//! | 1 | A check failed: `--fail-on-found`, `--max-allowed`, `--max-total` or `--max-tag` |
+//! | 2 | Usage or configuration error, such as an unknown flag, an invalid config file or pattern |
+//! | 3 | I/O error, such as a missing path or an unreadable file |
+
+use crate::thresholds::ThresholdError;
+
+pub const SUCCESS: i32 = 0;
+pub const CHECK_FAILED: i32 = 1;
+/// Also what clap exits with for invalid arguments.
+pub const USAGE_ERROR: i32 = 2;
+pub const IO_ERROR: i32 = 3;
+
+/// The table above, shown at the end of `--help`.
+pub const HELP: &str = "\
+Exit codes:
+  0  Success
+  1  A check failed: --fail-on-found, --max-allowed, --max-total or --max-tag
+  2  Usage or configuration error
+  3  I/O error, such as a missing path or an unreadable file";
+
+/// The exit code for an error returned by [`crate::run`].
+pub fn for_error(err: &anyhow::Error) -> i32 {
+    if err.downcast_ref::<ThresholdError>().is_some() {
+        CHECK_FAILED
+    } else if err.chain().any(|cause| cause.is::<std::io::Error>()) {
+        IO_ERROR
+    } else {
+        USAGE_ERROR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn classifies_errors_by_cause() {
+        let threshold = anyhow::Error::from(ThresholdError {
+            message: "Found 1 TODO items".to_string(),
+        });
+        assert_eq!(for_error(&threshold), CHECK_FAILED);
+
+        let io = std::fs::read_to_string("/nonexistent/todo-tree")
+            .context("Failed to read")
+            .unwrap_err();
+        assert_eq!(for_error(&io), IO_ERROR);
+
+        assert_eq!(for_error(&anyhow::anyhow!("bad pattern")), USAGE_ERROR);
+    }
+}
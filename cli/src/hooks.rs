@@ -0,0 +1,82 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Run a user-configured shell command in `root` before the walk starts.
+///
+/// The command goes through the platform shell (`sh -c` or `cmd /C`) so config
+/// values can use pipes and redirections. A nonzero exit status fails the run.
+pub fn run_pre_scan(command: &str, root: &Path) -> Result<()> {
+    let status = shell(command)
+        .current_dir(root)
+        .status()
+        .with_context(|| format!("Failed to run pre_scan_command: {}", command))?;
+
+    if !status.success() {
+        anyhow::bail!("pre_scan_command failed ({}): {}", status, command);
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.args(["/C", command]);
+    cmd
+}
+
+#[cfg(not(windows))]
+fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", command]);
+    cmd
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::parser::TodoParser;
+    use crate::scanner::{ScanOptions, Scanner};
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("todo_hooks_{name}_{unique}"));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn hook_output_is_picked_up_by_the_scan() {
+        let dir = temp_dir("generate");
+        run_pre_scan("echo '// TODO: generated by hook' > generated.rs", &dir).unwrap();
+
+        let parser = TodoParser::new(&["TODO".to_string()], true);
+        let result = Scanner::new(parser, ScanOptions::default())
+            .scan(&dir)
+            .unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        let files: Vec<_> = result
+            .files_map
+            .keys()
+            .filter_map(|p| p.file_name())
+            .collect();
+        assert_eq!(files, vec!["generated.rs"]);
+        assert_eq!(result.summary.total_count, 1);
+    }
+
+    #[test]
+    fn failing_hook_is_an_error() {
+        let dir = temp_dir("fail");
+        let err = run_pre_scan("exit 3", &dir).unwrap_err();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(err.to_string().contains("pre_scan_command failed"));
+    }
+}
@@ -0,0 +1,98 @@
+//! Which comment syntax a file is written in, from its extension or, for
+//! scripts without one, its `#!` line. Files matching neither are parsed
+//! permissively.
+
+use crate::comment::CommentSyntax;
+use std::path::Path;
+
+pub fn syntax_for_extension(extension: &str) -> Option<CommentSyntax> {
+    let syntax = match extension.to_ascii_lowercase().as_str() {
+        "rs" | "c" | "h" | "cc" | "cpp" | "hpp" | "cs" | "java" | "js" | "jsx" | "mjs" | "ts"
+        | "tsx" | "go" | "swift" | "kt" | "kts" | "scala" | "dart" => CommentSyntax::C_STYLE,
+        "py" | "pyi" => CommentSyntax::PYTHON,
+        "sh" | "bash" | "zsh" | "rb" | "pl" | "yml" | "yaml" | "toml" | "r" => CommentSyntax::HASH,
+        "html" | "htm" | "xml" | "svg" | "md" | "markdown" => CommentSyntax::MARKUP,
+        "sql" | "lua" | "hs" | "elm" => CommentSyntax::DOUBLE_DASH,
+        "lisp" | "el" | "clj" | "cljs" | "scm" | "asm" | "ini" => CommentSyntax::SEMICOLON,
+        _ => return None,
+    };
+
+    Some(syntax)
+}
+
+pub fn syntax_for_path(path: &Path) -> Option<CommentSyntax> {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .and_then(syntax_for_extension)
+}
+
+/// Syntax of the interpreter named by a `#!` line, e.g.
+/// `#!/usr/bin/env python3` or `#!/bin/bash -e`.
+pub fn syntax_for_shebang(line: &str) -> Option<CommentSyntax> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = words.find(|word| !word.starts_with('-'))?;
+    }
+    // `python3.12` and `pypy3` name the same syntax as `python`.
+    let name = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+
+    let syntax = match name {
+        "python" | "pypy" => CommentSyntax::PYTHON,
+        "sh" | "bash" | "zsh" | "dash" | "ksh" | "fish" | "ruby" | "perl" | "Rscript" => {
+            CommentSyntax::HASH
+        }
+        "node" | "deno" | "bun" => CommentSyntax::C_STYLE,
+        "lua" | "runghc" => CommentSyntax::DOUBLE_DASH,
+        _ => return None,
+    };
+
+    Some(syntax)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extensions_map_to_their_comment_markers() {
+        let line = |path: &str| syntax_for_path(Path::new(path)).map(|syntax| syntax.line);
+
+        assert_eq!(line("src/main.rs"), Some(&["//"][..]));
+        assert_eq!(line("tool.py"), Some(&["#"][..]));
+        assert_eq!(line("deploy.SH"), Some(&["#"][..]));
+        assert_eq!(line("query.sql"), Some(&["--"][..]));
+        assert_eq!(line("init.clj"), Some(&[";"][..]));
+        assert_eq!(
+            syntax_for_path(Path::new("README.md")),
+            Some(CommentSyntax::MARKUP)
+        );
+        assert_eq!(
+            syntax_for_path(Path::new("docs/guide.markdown")),
+            Some(CommentSyntax::MARKUP)
+        );
+        assert_eq!(syntax_for_path(Path::new("notes.unknown")), None);
+        assert_eq!(syntax_for_path(Path::new("Makefile")), None);
+    }
+
+    #[test]
+    fn shebang_names_the_interpreter() {
+        let syntax = syntax_for_shebang;
+
+        assert_eq!(
+            syntax("#!/usr/bin/env python3"),
+            Some(CommentSyntax::PYTHON)
+        );
+        assert_eq!(
+            syntax("#!/usr/bin/python3.12 -u"),
+            Some(CommentSyntax::PYTHON)
+        );
+        assert_eq!(syntax("#!/bin/bash -e"), Some(CommentSyntax::HASH));
+        assert_eq!(
+            syntax("#!/usr/bin/env -S node --no-warnings"),
+            Some(CommentSyntax::C_STYLE)
+        );
+        assert_eq!(syntax("#!/usr/bin/env unknown-tool"), None);
+        assert_eq!(syntax("# not a shebang"), None);
+    }
+}
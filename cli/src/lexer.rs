@@ -0,0 +1,229 @@
+use std::ops::Range;
+use std::path::Path;
+
+/// Languages with a real tokenizer for comments, used instead of the
+/// line-based heuristic in [`crate::comment`] when lexing is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    Python,
+    JavaScript,
+}
+
+impl Language {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        let language = match extension.to_ascii_lowercase().as_str() {
+            "rs" => Self::Rust,
+            "py" | "pyi" => Self::Python,
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => Self::JavaScript,
+            _ => return None,
+        };
+
+        Some(language)
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .and_then(Self::from_extension)
+    }
+}
+
+/// Blank out everything but comments, keeping line breaks, tabs and byte
+/// offsets so lines and columns still point into the original source.
+pub fn mask_non_comments(source: &str, language: Language) -> String {
+    let mut masked = String::with_capacity(source.len());
+    let mut last = 0;
+
+    for span in comment_spans(source, language) {
+        push_blank(&mut masked, &source[last..span.start]);
+        masked.push_str(&source[span.clone()]);
+        last = span.end;
+    }
+    push_blank(&mut masked, &source[last..]);
+
+    masked
+}
+
+fn push_blank(out: &mut String, code: &str) {
+    for c in code.chars() {
+        match c {
+            '\n' | '\r' | '\t' => out.push(c),
+            _ => out.extend(std::iter::repeat_n(' ', c.len_utf8())),
+        }
+    }
+}
+
+/// Byte ranges of the comments in `source`. Every delimiter is ASCII, so
+/// walking bytes never splits a character at a range boundary.
+fn comment_spans(source: &str, language: Language) -> Vec<Range<usize>> {
+    let bytes = source.as_bytes();
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        let end = match language {
+            Language::Rust | Language::JavaScript if rest.starts_with(b"//") => {
+                let end = line_end(bytes, i);
+                spans.push(i..end);
+                end
+            }
+            Language::Rust if rest.starts_with(b"/*") => {
+                let end = nested_block_end(bytes, i);
+                spans.push(i..end);
+                end
+            }
+            Language::JavaScript if rest.starts_with(b"/*") => {
+                let end = find(bytes, i + 2, b"*/").map_or(bytes.len(), |j| j + 2);
+                spans.push(i..end);
+                end
+            }
+            Language::Python if rest[0] == b'#' => {
+                let end = line_end(bytes, i);
+                spans.push(i..end);
+                end
+            }
+            Language::Rust => rust_literal_end(source, i),
+            Language::Python if rest.starts_with(b"\"\"\"") || rest.starts_with(b"'''") => {
+                triple_quoted_end(bytes, i)
+            }
+            Language::Python if matches!(rest[0], b'"' | b'\'') => {
+                quoted_end(bytes, i + 1, rest[0], false)
+            }
+            Language::JavaScript if matches!(rest[0], b'"' | b'\'') => {
+                quoted_end(bytes, i + 1, rest[0], false)
+            }
+            Language::JavaScript if rest[0] == b'`' => quoted_end(bytes, i + 1, b'`', true),
+            _ => i + 1,
+        };
+        i = end.max(i + 1);
+    }
+
+    spans
+}
+
+/// End of a Rust string, raw string or char literal starting at `i`, or
+/// `i + 1` when nothing starts there.
+fn rust_literal_end(source: &str, i: usize) -> usize {
+    let bytes = source.as_bytes();
+    let ident_start = |at: usize| at == 0 || !is_ident(bytes[at - 1]);
+
+    match bytes[i] {
+        b'"' => quoted_end(bytes, i + 1, b'"', true),
+        b'r' if ident_start(i) || (i > 0 && bytes[i - 1] == b'b' && ident_start(i - 1)) => {
+            let hashes = bytes[i + 1..].iter().take_while(|&&b| b == b'#').count();
+            if bytes.get(i + 1 + hashes) != Some(&b'"') {
+                return i + 1;
+            }
+            let mut closing = vec![b'"'];
+            closing.extend(std::iter::repeat_n(b'#', hashes));
+            find(bytes, i + 2 + hashes, &closing).map_or(bytes.len(), |j| j + closing.len())
+        }
+        // A quote is a char literal when a single (possibly escaped) char and
+        // a closing quote follow; otherwise it starts a lifetime.
+        b'\'' if bytes.get(i + 1) == Some(&b'\\') => quoted_end(bytes, i + 1, b'\'', false),
+        b'\'' => match source[i + 1..].chars().next() {
+            Some(c) if bytes.get(i + 1 + c.len_utf8()) == Some(&b'\'') => i + 2 + c.len_utf8(),
+            _ => i + 1,
+        },
+        _ => i + 1,
+    }
+}
+
+/// Index just past the closing `quote`, honoring backslash escapes. Single-line
+/// strings stop at the end of the line if left unterminated.
+fn quoted_end(bytes: &[u8], mut j: usize, quote: u8, multiline: bool) -> usize {
+    while j < bytes.len() {
+        match bytes[j] {
+            b'\\' => j += 2,
+            b'\n' if !multiline => return j,
+            b if b == quote => return j + 1,
+            _ => j += 1,
+        }
+    }
+    bytes.len()
+}
+
+fn triple_quoted_end(bytes: &[u8], i: usize) -> usize {
+    let delimiter = &bytes[i..i + 3];
+    let mut j = i + 3;
+    while j < bytes.len() {
+        if bytes[j] == b'\\' {
+            j += 2;
+        } else if bytes[j..].starts_with(delimiter) {
+            return j + 3;
+        } else {
+            j += 1;
+        }
+    }
+    bytes.len()
+}
+
+fn nested_block_end(bytes: &[u8], i: usize) -> usize {
+    let mut depth = 0;
+    let mut j = i;
+    while j < bytes.len() {
+        if bytes[j..].starts_with(b"/*") {
+            depth += 1;
+            j += 2;
+        } else if bytes[j..].starts_with(b"*/") {
+            depth -= 1;
+            j += 2;
+            if depth == 0 {
+                return j;
+            }
+        } else {
+            j += 1;
+        }
+    }
+    bytes.len()
+}
+
+fn line_end(bytes: &[u8], i: usize) -> usize {
+    find(bytes, i, b"\n").unwrap_or(bytes.len())
+}
+
+fn find(bytes: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    bytes
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|pos| from + pos)
+}
+
+fn is_ident(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_only_comments_at_their_offsets() {
+        let source = "let s = \"// no\"; // yes\nlet r = r#\"/* no */\"#; /* a /* nested */ yes */\nlet c = '\"'; let l: &'a str = \"\"; // end";
+        let masked = mask_non_comments(source, Language::Rust);
+
+        assert_eq!(masked.len(), source.len());
+        let lines: Vec<&str> = masked.lines().collect();
+        assert_eq!(lines[0].trim(), "// yes");
+        assert_eq!(
+            lines[0].find("//"),
+            source.lines().next().unwrap().rfind("//")
+        );
+        assert_eq!(lines[1].trim(), "/* a /* nested */ yes */");
+        assert_eq!(lines[2].trim(), "// end");
+    }
+
+    #[test]
+    fn python_and_javascript_strings_hide_markers() {
+        let python = "x = '# no'  # yes\ns = \"\"\"\n# still a string\n\"\"\"\n";
+        let masked = mask_non_comments(python, Language::Python);
+        assert_eq!(masked.split_whitespace().collect::<Vec<_>>(), ["#", "yes"]);
+
+        let js = "const t = `\n// no\n`; const u = 'a//b'; /* yes */";
+        let masked = mask_non_comments(js, Language::JavaScript);
+        assert_eq!(masked.trim(), "/* yes */");
+    }
+}
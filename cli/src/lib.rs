@@ -1,26 +1,46 @@
+pub mod blame;
+pub mod cache;
 pub mod cli;
 pub mod commands;
+pub mod comment;
 pub mod config;
+pub mod exit_codes;
+pub mod hooks;
+pub mod language;
+pub mod lexer;
 pub mod parser;
 pub mod printer;
+pub mod resolved;
 pub mod scanner;
+pub mod thresholds;
 pub mod utils;
+pub mod watch;
 
 use anyhow::Result;
 use clap::Parser;
 use cli::{Cli, Commands};
-use commands::{init, list, scan, stats, tags as cli_tags, workflow};
+use commands::{bench, init, list, scan, stats, tags as cli_tags, workflow};
 pub use todo_tree_core::{Priority, ScanResult, ScanSummary, TodoItem};
 
 pub fn run() -> Result<()> {
     let cli = Cli::parse();
 
-    if cli.global.no_color || std::env::var("NO_COLOR").is_ok() {
+    if cli.global.no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
         colored::control::set_override(false);
     }
 
+    if cli.print_schema {
+        let schema = printer::schema::output_schema();
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    }
+
+    if let Some(file) = &cli.bench_parse {
+        return bench::run(file, cli.global.config.as_deref());
+    }
+
     match cli.get_command() {
-        Commands::Scan(args) => scan::run(args, &cli.global),
+        Commands::Scan(args) => scan::run(*args, &cli.global),
         Commands::List(args) => list::run(args, &cli.global),
         Commands::Tags(args) => cli_tags::run(args, &cli.global),
         Commands::Init(args) => init::run(args),
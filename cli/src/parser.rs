@@ -1,12 +1,107 @@
+use crate::comment::CommentSyntax;
+use crate::language;
+use crate::lexer::{Language, mask_non_comments};
+use aho_corasick::AhoCorasick;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
 use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
+use std::sync::LazyLock;
 use todo_tree_core::{DEFAULT_REGEX, Priority, TodoItem};
 
+/// Tag count above which lines are prefiltered before running the pattern.
+const PREFILTER_MIN_TAGS: usize = 32;
+
+/// Byte order mark some Windows editors put at the start of UTF-8 files.
+const BOM: &str = "\u{feff}";
+
+static LINE_RANGE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\blines?\s+(\d+)\s*[-–]\s*(\d+)\b").expect("valid line range regex")
+});
+
+static DUE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bdue:(\d{4}-\d{2}-\d{2})\b").expect("valid due date regex"));
+
+static ISSUE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|[^\w#])(#\d+|[A-Z]+-\d+)\b").expect("valid issue regex"));
+
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    /// Match tags exactly as configured (`TODO:` but not `todo:`). On by
+    /// default, the inverse of [`crate::config::Config::ignore_case`].
+    pub case_sensitive: bool,
+    pub require_colon: bool,
+    /// Regex replacing the built-in pattern. `$TAGS` expands to the escaped tag
+    /// alternation. The tag is read from a `tag` capture group, plus optional
+    /// `author` and `message` groups; patterns without named groups use groups
+    /// 2, 3 and 4 instead.
+    pub custom_regex: Option<String>,
+    /// Match a tag that ends the line with no message, even when a colon is required.
+    pub allow_empty: bool,
+    /// Only report tags inside comments for files whose language is known.
+    /// Files of unknown type are always matched permissively.
+    pub comments_only: bool,
+    /// Skip tags inside a URL token, e.g. `https://example.com/docs#TODO`.
+    pub skip_urls: bool,
+    /// Append the lines continuing the tag's comment to the item's body: `*`
+    /// lines of a block comment, or following lines with the same line-comment
+    /// marker, up to a blank comment line or code.
+    pub multiline: bool,
+    /// Tokenize Rust, Python and JavaScript/TypeScript files and match only
+    /// inside real comments, instead of the per-line comment heuristic.
+    pub lex_comments: bool,
+    /// Read a `[P0]`–`[P4]` or `[high]` style marker before the colon or at
+    /// the start of the message as the item's priority, and drop it from the
+    /// message.
+    pub inline_priority: bool,
+    /// Priority overrides by tag, consulted before [`Priority::from_tag`].
+    pub tag_priorities: HashMap<String, Priority>,
+    /// Expand tabs to this width when computing the reported column.
+    pub tab_width: Option<usize>,
+    /// Capture this many source lines before and after each tag line.
+    pub context_lines: usize,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            case_sensitive: true,
+            require_colon: true,
+            custom_regex: None,
+            allow_empty: false,
+            comments_only: true,
+            skip_urls: false,
+            multiline: false,
+            lex_comments: false,
+            inline_priority: false,
+            tag_priorities: HashMap::new(),
+            tab_width: None,
+            context_lines: 0,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TodoParser {
     pattern: Option<Regex>,
+    /// Cheap check that a line contains some tag before running `pattern`,
+    /// built for large tag sets where the alternation gets slow.
+    prefilter: Option<AhoCorasick>,
+    named_groups: bool,
     tags: Vec<String>,
     case_sensitive: bool,
+    comments_only: bool,
+    skip_urls: bool,
+    multiline: bool,
+    lex_comments: bool,
+    inline_priority: bool,
+    /// Keyed by upper-cased tag.
+    tag_priorities: HashMap<String, Priority>,
+    tab_width: Option<usize>,
+    context_lines: usize,
 }
 
 impl TodoParser {
@@ -14,61 +109,266 @@ impl TodoParser {
         Self::with_options(tags, case_sensitive, true, None)
     }
 
+    /// Panics if `custom_regex` is invalid; use [`TodoParser::from_options`] to
+    /// handle that case.
     pub fn with_options(
         tags: &[String],
         case_sensitive: bool,
         require_colon: bool,
         custom_regex: Option<&str>,
     ) -> Self {
-        let pattern = Self::build_pattern(tags, case_sensitive, require_colon, custom_regex);
-        Self {
+        Self::from_options(
+            tags,
+            ParserOptions {
+                case_sensitive,
+                require_colon,
+                custom_regex: custom_regex.map(str::to_string),
+                ..Default::default()
+            },
+        )
+        .expect("Failed to build regex pattern")
+    }
+
+    /// Build a parser, failing if `options.custom_regex` does not compile or
+    /// has no `tag` capture group.
+    pub fn from_options(tags: &[String], options: ParserOptions) -> Result<Self> {
+        // Stray whitespace is easy to leave in YAML lists and would otherwise
+        // end up inside the regex, so `"TODO "` never matches.
+        let tags: Vec<String> = tags
+            .iter()
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect();
+        let pattern = Self::build_pattern(&tags, &options)?;
+        let prefilter = Self::build_prefilter(&tags, &options);
+        let named_groups = pattern
+            .as_ref()
+            .is_some_and(|p| p.capture_names().any(|name| name == Some("tag")));
+
+        Ok(Self {
             pattern,
-            tags: tags.to_vec(),
-            case_sensitive,
-        }
+            prefilter,
+            named_groups,
+            tags,
+            case_sensitive: options.case_sensitive,
+            comments_only: options.comments_only,
+            skip_urls: options.skip_urls,
+            multiline: options.multiline,
+            lex_comments: options.lex_comments,
+            inline_priority: options.inline_priority,
+            tag_priorities: options
+                .tag_priorities
+                .into_iter()
+                .map(|(tag, priority)| (tag.trim().to_uppercase(), priority))
+                .collect(),
+            tab_width: options.tab_width.filter(|width| *width > 0),
+            context_lines: options.context_lines,
+        })
     }
 
-    fn build_pattern(
-        tags: &[String],
-        case_sensitive: bool,
-        require_colon: bool,
-        custom_regex: Option<&str>,
-    ) -> Option<Regex> {
+    fn build_pattern(tags: &[String], options: &ParserOptions) -> Result<Option<Regex>> {
         if tags.is_empty() {
-            return None;
+            return Ok(None);
         }
 
         let escaped_tags: Vec<String> = tags.iter().map(|t| regex::escape(t)).collect();
         let tags_alternation = escaped_tags.join("|");
 
+        let custom_regex = options.custom_regex.as_deref();
         let mut base_pattern = custom_regex.unwrap_or(DEFAULT_REGEX).to_string();
-        if custom_regex.is_none() && !require_colon {
-            base_pattern = base_pattern.replace(":(.*)", r"(?:\s*$|(?:(?::|\s+)(.*)))");
+        if custom_regex.is_none() && options.inline_priority {
+            base_pattern = base_pattern.replace(
+                r"\))?",
+                r"\))?(?:\s*\[(?P<priority>(?i:p[0-4]|low|medium|high|critical))\])?",
+            );
+        }
+        if custom_regex.is_none() && !options.require_colon {
+            base_pattern =
+                base_pattern.replace(":(?P<message>.*)", r"(?:\s*$|(?:(?::|\s+)(?P<message>.*)))");
+        } else if custom_regex.is_none() && options.allow_empty {
+            base_pattern = base_pattern.replace(":(?P<message>.*)", r"(?::(?P<message>.*)|\s*$)");
         }
 
         let pattern_string = base_pattern.replace("$TAGS", &tags_alternation);
         let regex = RegexBuilder::new(&pattern_string)
-            .case_insensitive(!case_sensitive)
+            .case_insensitive(!options.case_sensitive)
             .multi_line(true)
             .build()
-            .expect("Failed to build regex pattern");
+            .with_context(|| format!("Invalid tag pattern: {}", pattern_string))?;
+
+        let has_tag_group = regex.capture_names().any(|name| name == Some("tag"));
+        if !has_tag_group && regex.captures_len() < 3 {
+            anyhow::bail!(
+                "Tag pattern must define a `tag` capture group: {}",
+                pattern_string
+            );
+        }
 
-        Some(regex)
+        Ok(Some(regex))
+    }
+
+    /// An Aho-Corasick automaton over the tags once there are more than
+    /// [`PREFILTER_MIN_TAGS`]. A line without any tag literal can never match
+    /// the default pattern, so it is skipped without touching the regex.
+    ///
+    /// Custom patterns may match without a tag literal (inline flags, no
+    /// `$TAGS`), and ASCII case folding differs from the regex's Unicode
+    /// folding for non-ASCII tags, so both keep the plain regex path.
+    fn build_prefilter(tags: &[String], options: &ParserOptions) -> Option<AhoCorasick> {
+        let unicode_folding = !options.case_sensitive && !tags.iter().all(|t| t.is_ascii());
+        if tags.len() <= PREFILTER_MIN_TAGS || options.custom_regex.is_some() || unicode_folding {
+            return None;
+        }
+
+        AhoCorasick::builder()
+            .ascii_case_insensitive(!options.case_sensitive)
+            .build(tags)
+            .ok()
+    }
+
+    /// Look up a capture by name, or by position for patterns without named groups.
+    fn group<'h>(
+        &self,
+        captures: &regex::Captures<'h>,
+        name: &str,
+        index: usize,
+    ) -> Option<regex::Match<'h>> {
+        if self.named_groups {
+            captures.name(name)
+        } else {
+            captures.get(index)
+        }
     }
 
     pub fn parse_line(&self, line: &str, line_number: usize) -> Option<TodoItem> {
+        self.parse_line_from(line, line_number, 0)
+    }
+
+    /// Parse a line, only accepting tags that sit inside a comment of `syntax`.
+    /// Without a syntax this is the same as [`TodoParser::parse_line`].
+    pub fn parse_line_with_syntax(
+        &self,
+        line: &str,
+        line_number: usize,
+        syntax: Option<&CommentSyntax>,
+    ) -> Option<TodoItem> {
+        let start = match syntax {
+            Some(syntax) => syntax.comment_start(line)?,
+            None => 0,
+        };
+        self.parse_line_from(line, line_number, start)
+    }
+
+    /// Parse a line that starts inside a block comment of `syntax`. The text up
+    /// to the closing marker is comment even without a marker of its own, and
+    /// only comments in the code after it can match.
+    fn parse_line_in_block(
+        &self,
+        line: &str,
+        line_number: usize,
+        syntax: &CommentSyntax,
+    ) -> Option<TodoItem> {
+        let (open, close) = syntax.block?;
+        let block_end = syntax.block_end(line);
+        let inside = &line[..block_end.map_or(line.len(), |end| end - close.len())];
+
+        let item = self.parse_line_from(inside, line_number, 0).or_else(|| {
+            // Bare `TODO:` lines have no marker for the pattern to anchor on,
+            // so match them as if they followed the opening marker.
+            let prefix = format!("{} ", open);
+            let mut item =
+                self.parse_line_from(&format!("{}{}", prefix, inside), line_number, 0)?;
+            let raw_column = item
+                .raw_column
+                .unwrap_or(item.column)
+                .checked_sub(prefix.chars().count())?;
+            item.column = match self.tab_width {
+                Some(width) => display_column(&line[..byte_offset(line, raw_column)], width),
+                None => raw_column,
+            };
+            item.raw_column = self.tab_width.map(|_| raw_column);
+            Some(item)
+        });
+        if let Some(mut item) = item {
+            item.line_content = Some(line.to_string());
+            return Some(item);
+        }
+
+        let code_start = block_end?;
+        let start = code_start + syntax.marker_start(&line[code_start..])?;
+        self.parse_line_from(line, line_number, start)
+    }
+
+    /// Parse a line with `syntax`, knowing whether a block comment is open at its start.
+    fn parse_line_in_context(
+        &self,
+        line: &str,
+        line_number: usize,
+        syntax: Option<&CommentSyntax>,
+        in_block: bool,
+    ) -> Option<TodoItem> {
+        match syntax {
+            Some(syntax) if in_block => self.parse_line_in_block(line, line_number, syntax),
+            _ => self.parse_line_with_syntax(line, line_number, syntax),
+        }
+    }
+
+    fn parse_line_from(&self, line: &str, line_number: usize, start: usize) -> Option<TodoItem> {
         let pattern = self.pattern.as_ref()?;
-        if let Some(captures) = pattern.captures(line) {
-            let tag_match = captures.get(2)?;
-            let author = captures.get(3).map(|m| m.as_str().to_string());
-            let message = captures
-                .get(4)
+        if let Some(prefilter) = &self.prefilter
+            && !prefilter.is_match(&line[start..])
+        {
+            return None;
+        }
+        let mut search_from = start;
+
+        while let Some(captures) = pattern.captures_at(line, search_from) {
+            let tag_match = self.group(&captures, "tag", 2)?;
+
+            // The regex crate has no lookahead, so reject tags that continue
+            // into a longer word (`TODOS`, `NOTES`), or sit in a URL when
+            // asked to, here and keep searching.
+            if continues_word(line, tag_match.end())
+                || (self.skip_urls && inside_url(line, tag_match.start()))
+            {
+                let skip = line[tag_match.start()..].chars().next()?.len_utf8();
+                search_from = tag_match.start() + skip;
+                continue;
+            }
+
+            // A date in the parentheses is a deadline, not an assignee.
+            let (dates, authors): (Vec<String>, Vec<String>) = self
+                .group(&captures, "author", 3)
+                .map(|m| split_authors(m.as_str()))
+                .unwrap_or_default()
+                .into_iter()
+                .partition(|a| parse_date(a).is_some());
+            let author = authors.first().cloned();
+            let raw_author = self.group(&captures, "author", 3).map(|m| m.as_str());
+            let mut message = self
+                .group(&captures, "message", 4)
                 .map(|m| m.as_str().trim().to_string())
                 .unwrap_or_default();
+            let mut inline_priority = None;
+            if self.inline_priority {
+                inline_priority = captures
+                    .name("priority")
+                    .and_then(|m| marker_priority(m.as_str()));
+                if let Some((priority, rest)) = leading_priority_marker(&message) {
+                    inline_priority = inline_priority.or(Some(priority));
+                    message = rest.to_string();
+                }
+            }
 
             let tag = tag_match.as_str().to_string();
-            let column = tag_match.start() + 1;
+            let raw_column = char_column(line, tag_match.start());
+            let column = match self.tab_width {
+                Some(width) => display_column(&line[..tag_match.start()], width),
+                None => raw_column,
+            };
 
+            let written_tag = tag.clone();
             let normalized_tag = if self.case_sensitive {
                 tag
             } else {
@@ -79,16 +379,41 @@ impl TodoParser {
                     .unwrap_or(tag)
             };
 
-            let priority = Priority::from_tag(&normalized_tag);
+            let priority = inline_priority
+                .or_else(|| {
+                    self.tag_priorities
+                        .get(&normalized_tag.to_uppercase())
+                        .copied()
+                })
+                .unwrap_or_else(|| Priority::from_tag(&normalized_tag));
+            let referenced_lines = parse_line_range(&message);
+            let issue = parse_issue(&message).or_else(|| raw_author.and_then(parse_issue));
+            let due = dates
+                .first()
+                .and_then(|date| parse_date(date))
+                .or_else(|| parse_due(&message));
 
             return Some(TodoItem {
+                matched_tag: (written_tag != normalized_tag).then_some(written_tag),
                 tag: normalized_tag,
                 message,
                 line: line_number,
                 column,
+                raw_column: self.tab_width.map(|_| raw_column),
                 line_content: Some(line.to_string()),
                 author,
+                authors,
                 priority,
+                referenced_lines,
+                issue,
+                due,
+                blame_author: None,
+                blame_date: None,
+                body: Vec::new(),
+                context_before: Vec::new(),
+                context_after: Vec::new(),
+                duplicates: Vec::new(),
+                id: None,
             });
         }
 
@@ -96,21 +421,414 @@ impl TodoParser {
     }
 
     pub fn parse_content(&self, content: &str) -> Vec<TodoItem> {
-        content
-            .lines()
+        self.parse_content_with_syntax(content, None)
+    }
+
+    pub fn parse_content_with_syntax(
+        &self,
+        content: &str,
+        syntax: Option<&CommentSyntax>,
+    ) -> Vec<TodoItem> {
+        self.parse_lines(content_lines(content), syntax)
+    }
+
+    fn parse_lines<I, S>(&self, lines: I, syntax: Option<&CommentSyntax>) -> Vec<TodoItem>
+    where
+        I: Iterator<Item = S>,
+        S: AsRef<str>,
+    {
+        if self.context_lines == 0 {
+            return self.match_lines(lines, syntax);
+        }
+
+        // Context needs lines after each match, so keep the whole file.
+        let lines: Vec<String> = lines.map(|line| line.as_ref().to_string()).collect();
+        let mut items = self.match_lines(lines.iter(), syntax);
+        self.attach_context(&mut items, &lines);
+        items
+    }
+
+    /// Fill each item's surrounding lines from `lines`, clamped to the file.
+    fn attach_context<S: AsRef<str>>(&self, items: &mut [TodoItem], lines: &[S]) {
+        if self.context_lines == 0 {
+            return;
+        }
+
+        let text = |range: std::ops::Range<usize>| -> Vec<String> {
+            lines[range]
+                .iter()
+                .map(|line| line.as_ref().to_string())
+                .collect()
+        };
+        for item in items {
+            let idx = item.line - 1;
+            let after_end = (item.line + self.context_lines).min(lines.len());
+            item.context_before = text(idx.saturating_sub(self.context_lines)..idx);
+            item.context_after = text(item.line.min(after_end)..after_end);
+        }
+    }
+
+    fn match_lines<I, S>(&self, lines: I, syntax: Option<&CommentSyntax>) -> Vec<TodoItem>
+    where
+        I: Iterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut lines = lines
             .enumerate()
-            .filter_map(|(idx, line)| self.parse_line(line, idx + 1))
-            .collect()
+            .map(|(idx, line)| (idx + 1, line))
+            .peekable();
+        let mut items = Vec::new();
+        let mut open_block = false;
+        let block_state = |line: &str, open_block: bool| {
+            syntax.is_some_and(|syntax| syntax.ends_in_block(line, open_block))
+        };
+
+        while let Some((line_number, line)) = lines.next() {
+            let line = line.as_ref();
+            let starts_in_block = open_block;
+            open_block = block_state(line, open_block);
+            let Some(mut item) =
+                self.parse_line_in_context(line, line_number, syntax, starts_in_block)
+            else {
+                continue;
+            };
+
+            let tag_start = tag_offset(line, &item);
+            let in_block = self.multiline && continues_in_block(line, tag_start);
+            let marker = if self.multiline && !in_block {
+                line_comment_marker(line, tag_start)
+            } else {
+                None
+            };
+
+            if in_block || marker.is_some() {
+                // The tag line alone supplies tag and author; continuation lines
+                // only extend the body and are never matched as items themselves.
+                while let Some((next_number, next)) = lines.peek() {
+                    let next = next.as_ref();
+                    let text = match marker {
+                        Some(marker) => line_continuation(next, marker),
+                        None => block_continuation(next),
+                    };
+                    let Some(text) = text else {
+                        break;
+                    };
+                    if text.is_empty()
+                        || self
+                            .parse_line_in_context(next, *next_number, syntax, open_block)
+                            .is_some()
+                    {
+                        break;
+                    }
+                    item.body.push(text.to_string());
+                    let closes_block = in_block && next.contains("*/");
+                    open_block = block_state(next, open_block);
+                    lines.next();
+                    if closes_block {
+                        break;
+                    }
+                }
+            }
+
+            items.push(item);
+        }
+
+        items
     }
 
+    /// Parse a file line by line so memory stays bounded by the longest line.
+    ///
+    /// Lines that are not valid UTF-8 are skipped; a NUL byte marks the file
+    /// as binary and fails it with [`std::io::ErrorKind::InvalidData`].
     pub fn parse_file(&self, path: &Path) -> std::io::Result<Vec<TodoItem>> {
-        let content = std::fs::read_to_string(path)?;
-        Ok(self.parse_content(&content))
+        if self.lex_comments
+            && let Some(language) = Language::from_path(path)
+        {
+            let bytes = std::fs::read(path)?;
+            if bytes.contains(&0) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "binary file",
+                ));
+            }
+            return Ok(self.parse_lexed(&String::from_utf8_lossy(&bytes), language));
+        }
+
+        let mut reader = BufReader::new(File::open(path)?);
+        let syntax = if self.comments_only {
+            language::syntax_for_path(path).or_else(|| shebang_syntax(&mut reader))
+        } else {
+            None
+        };
+        self.parse_buffered(reader, syntax.as_ref())
+    }
+
+    /// Parse only the comments of `source`, as tokenized for `language`. Tags
+    /// in string literals are never reported, even in strings spanning lines.
+    pub fn parse_lexed(&self, source: &str, language: Language) -> Vec<TodoItem> {
+        let source = source.strip_prefix(BOM).unwrap_or(source);
+        let masked = mask_non_comments(source, language);
+        let masked_lines: Vec<&str> = content_lines(&masked).collect();
+        let mut items = self.match_lines(masked_lines.iter(), None);
+
+        // Masking keeps byte offsets but turns multibyte characters into
+        // several spaces, so columns are recounted on the original text.
+        let lines: Vec<&str> = content_lines(source).collect();
+        for item in &mut items {
+            let Some(line) = lines.get(item.line - 1) else {
+                continue;
+            };
+            let offset = tag_offset(masked_lines[item.line - 1], item);
+            item.column = match self.tab_width {
+                Some(width) => display_column(&line[..offset], width),
+                None => char_column(line, offset),
+            };
+            if item.raw_column.is_some() {
+                item.raw_column = Some(char_column(line, offset));
+            }
+            item.line_content = Some(line.to_string());
+        }
+        self.attach_context(&mut items, &lines);
+
+        items
+    }
+
+    /// Parse everything `reader` yields, e.g. stdin. There is no file name, so
+    /// no language-specific comment syntax applies.
+    pub fn parse_reader<R: Read>(&self, reader: R) -> std::io::Result<Vec<TodoItem>> {
+        self.parse_buffered(BufReader::new(reader), None)
+    }
+
+    fn parse_buffered<R: BufRead>(
+        &self,
+        mut reader: R,
+        syntax: Option<&CommentSyntax>,
+    ) -> std::io::Result<Vec<TodoItem>> {
+        let mut buf = Vec::new();
+        let mut error = None;
+        let mut first = true;
+        let lines = std::iter::from_fn(|| {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) => None,
+                Ok(_) if buf.contains(&0) => {
+                    error = Some(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "binary file",
+                    ));
+                    None
+                }
+                Ok(_) => {
+                    let line = buf.strip_suffix(b"\n").unwrap_or(&buf);
+                    let line = line.strip_suffix(b"\r").unwrap_or(line);
+                    let line = if std::mem::take(&mut first) {
+                        line.strip_prefix(BOM.as_bytes()).unwrap_or(line)
+                    } else {
+                        line
+                    };
+                    // Keep the line count right for invalid lines by yielding them empty.
+                    Some(String::from_utf8(line.to_vec()).unwrap_or_default())
+                }
+                Err(err) => {
+                    error = Some(err);
+                    None
+                }
+            }
+        });
+
+        let items = self.parse_lines(lines, syntax);
+        match error {
+            Some(err) => Err(err),
+            None => Ok(items),
+        }
     }
 
     pub fn tags(&self) -> &[String] {
         &self.tags
     }
+
+    /// Every setting that changes what this parser reports, as one string.
+    /// Cached items are only reused by a parser with the same key.
+    pub fn cache_key(&self) -> String {
+        let mut priorities: Vec<_> = self.tag_priorities.iter().collect();
+        priorities.sort();
+        format!(
+            "{:?} {:?} {} {} {} {} {} {} {:?} {:?} {}",
+            self.pattern.as_ref().map(Regex::as_str),
+            self.tags,
+            self.case_sensitive,
+            self.comments_only,
+            self.skip_urls,
+            self.multiline,
+            self.lex_comments,
+            self.inline_priority,
+            priorities,
+            self.tab_width,
+            self.context_lines
+        )
+    }
+}
+
+/// Comment syntax named by a `#!` first line, peeked without consuming it.
+fn shebang_syntax<R: BufRead>(reader: &mut R) -> Option<CommentSyntax> {
+    let buf = reader.fill_buf().ok()?;
+    let buf = buf.strip_prefix(BOM.as_bytes()).unwrap_or(buf);
+    let line = buf.split(|&b| b == b'\n').next()?;
+    language::syntax_for_shebang(std::str::from_utf8(line).ok()?.trim_end())
+}
+
+/// Whether the tag ending at `end` is immediately followed by another word character.
+fn continues_word(line: &str, end: usize) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let before = line[..end].chars().next_back();
+    let after = line[end..].chars().next();
+    before.is_some_and(is_word) && after.is_some_and(is_word)
+}
+
+/// Whether the tag at byte offset `start` is part of a URL: the token it sits
+/// in, up to whitespace, a quote or a bracket, contains `://` before it.
+fn inside_url(line: &str, start: usize) -> bool {
+    line[..start]
+        .rsplit(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '(' | '<' | '['))
+        .next()
+        .is_some_and(|token| token.contains("://"))
+}
+
+/// Whether the tag at byte offset `tag_start` sits in a `/* */` block that is
+/// still open at the end of the line.
+fn continues_in_block(line: &str, tag_start: usize) -> bool {
+    let (before, after) = line.split_at(tag_start);
+    (before.contains("/*") || before.trim_start().starts_with('*')) && !after.contains("*/")
+}
+
+/// Text of a JSDoc-style ` * text` continuation line, without the closing `*/`.
+fn block_continuation(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("*/") {
+        return None;
+    }
+    let text = trimmed.strip_prefix('*')?.trim();
+    Some(text.strip_suffix("*/").unwrap_or(text).trim_end())
+}
+
+/// Marker of the line comment holding the tag, e.g. `//`, `///` or `#`, when
+/// it sits directly before the tag.
+fn line_comment_marker(line: &str, tag_start: usize) -> Option<&str> {
+    let before = line[..tag_start].trim_end();
+    let marker = &before[before.trim_end_matches(['/', '#', '-', ';', '!']).len()..];
+    ["//", "#", "--", ";"]
+        .iter()
+        .any(|m| marker.starts_with(m))
+        .then_some(marker)
+}
+
+/// Text of a line continuing a `marker` comment. A longer run of marker
+/// characters (`///` after `//`) starts a different comment.
+fn line_continuation<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = line.trim_start().strip_prefix(marker)?;
+    if rest.starts_with(['/', '#', '-', ';', '!']) {
+        return None;
+    }
+    Some(rest.trim())
+}
+
+/// Split the parenthesized assignee list on `,`, `&` and whitespace, e.g.
+/// `alice, bob & carol` or `alice bob`.
+fn split_authors(raw: &str) -> Vec<String> {
+    raw.split(|c: char| c == ',' || c == '&' || c.is_whitespace())
+        .filter(|a| !a.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Lines of `content` without a leading BOM or any `\r` line-ending residue,
+/// including on a last line that has no `\n`.
+fn content_lines(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .strip_prefix(BOM)
+        .unwrap_or(content)
+        .lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+}
+
+/// 1-based column of the character starting at byte `offset` in `line`,
+/// counting Unicode scalar values rather than bytes.
+fn char_column(line: &str, offset: usize) -> usize {
+    line[..offset].chars().count() + 1
+}
+
+/// Byte offset of the character at 1-based `column` in `line`; the inverse
+/// of [`char_column`].
+fn byte_offset(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .nth(column - 1)
+        .map_or(line.len(), |(offset, _)| offset)
+}
+
+/// Byte offset of `item`'s tag in `line`, the line it was matched on.
+fn tag_offset(line: &str, item: &TodoItem) -> usize {
+    byte_offset(line, item.raw_column.unwrap_or(item.column))
+}
+
+/// 1-based editor column after `prefix`, with tabs advancing to the next
+/// multiple of `tab_width`.
+fn display_column(prefix: &str, tab_width: usize) -> usize {
+    let width = prefix.chars().fold(0, |col, ch| {
+        if ch == '\t' {
+            col + tab_width - col % tab_width
+        } else {
+            col + 1
+        }
+    });
+    width + 1
+}
+
+/// Extract a `lines N-M` reference from a message, if it names a valid range.
+fn parse_line_range(message: &str) -> Option<(usize, usize)> {
+    let captures = LINE_RANGE_REGEX.captures(message)?;
+    let start = captures.get(1)?.as_str().parse().ok()?;
+    let end = captures.get(2)?.as_str().parse().ok()?;
+    (start <= end).then_some((start, end))
+}
+
+/// Priority named by an inline marker: `P0` is Critical, `P1` High, `P2`
+/// Medium and `P3`/`P4` Low; priority names are accepted too.
+fn marker_priority(marker: &str) -> Option<Priority> {
+    match marker.trim().to_ascii_uppercase().as_str() {
+        "P0" => Some(Priority::Critical),
+        "P1" => Some(Priority::High),
+        "P2" => Some(Priority::Medium),
+        "P3" | "P4" => Some(Priority::Low),
+        name if name.chars().all(|c| c.is_ascii_alphabetic()) => name.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Split a leading `[P1]`-style marker off a message.
+fn leading_priority_marker(message: &str) -> Option<(Priority, &str)> {
+    let (marker, rest) = message.strip_prefix('[')?.split_once(']')?;
+    Some((marker_priority(marker)?, rest.trim_start()))
+}
+
+/// Parse an ISO `YYYY-MM-DD` date.
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()
+}
+
+/// Extract the date of a `due:YYYY-MM-DD` token in a message.
+fn parse_due(message: &str) -> Option<NaiveDate> {
+    DUE_REGEX
+        .captures(message)
+        .and_then(|c| c.get(1))
+        .and_then(|m| parse_date(m.as_str()))
+}
+
+/// Extract the first `#123` or Jira-style `PROJ-42` issue reference.
+fn parse_issue(text: &str) -> Option<String> {
+    ISSUE_REGEX
+        .captures(text)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().to_string())
 }
 
 #[cfg(test)]
@@ -187,6 +905,46 @@ mod tests {
         assert_eq!(item.priority, Priority::from_tag("FIXME"));
     }
 
+    #[test]
+    fn parse_line_splits_multiple_authors() {
+        let parser = TodoParser::new(&tags(), true);
+        let item = parser
+            .parse_line("// TODO(alice, bob): x", 1)
+            .expect("expected TODO item");
+
+        assert_eq!(item.authors, vec!["alice".to_string(), "bob".to_string()]);
+        assert_eq!(item.author.as_deref(), Some("alice"));
+        assert_eq!(item.message, "x");
+
+        let item = parser
+            .parse_line("// TODO(alice & bob): x", 1)
+            .expect("expected TODO item");
+        assert_eq!(item.authors, vec!["alice".to_string(), "bob".to_string()]);
+
+        let item = parser
+            .parse_line("// TODO(alice bob,carol): x", 1)
+            .expect("expected TODO item");
+        assert_eq!(item.authors, ["alice", "bob", "carol"]);
+        assert!(item.has_author("bob") && item.has_author("CAROL"));
+        assert!(!item.has_author("alice bob"));
+    }
+
+    #[test]
+    fn parse_line_extracts_referenced_line_range() {
+        let parser = TodoParser::new(&tags(), true);
+        let item = parser
+            .parse_line("// TODO: see lines 40-52 for the old logic", 1)
+            .expect("expected TODO item");
+
+        assert_eq!(item.referenced_lines, Some((40, 52)));
+        assert_eq!(item.message, "see lines 40-52 for the old logic");
+
+        let item = parser
+            .parse_line("// TODO: nothing to see here", 2)
+            .expect("expected TODO item");
+        assert_eq!(item.referenced_lines, None);
+    }
+
     #[test]
     fn parse_line_trims_message() {
         let parser = custom_parser(&tags(), true);
@@ -260,6 +1018,196 @@ nothing here
         assert_eq!(items[2].line, 5);
     }
 
+    #[test]
+    fn tab_width_expands_tabs_in_reported_column() {
+        let parser = TodoParser::from_options(
+            &tags(),
+            ParserOptions {
+                tab_width: Some(4),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let items =
+            parser.parse_content("\t// TODO: one tab\n\t\t// FIXME: two tabs\n  \t// BUG: mixed");
+
+        assert_eq!(items.len(), 3);
+        assert_eq!((items[0].column, items[0].raw_column), (8, Some(5)));
+        assert_eq!((items[1].column, items[1].raw_column), (12, Some(6)));
+        assert_eq!((items[2].column, items[2].raw_column), (8, Some(7)));
+
+        let raw = custom_parser(&tags(), false).parse_content("\t// TODO: one tab");
+        assert_eq!((raw[0].column, raw[0].raw_column), (5, None));
+    }
+
+    #[test]
+    fn skip_urls_ignores_tags_inside_links_only() {
+        let options = |skip_urls| ParserOptions {
+            skip_urls,
+            comments_only: false,
+            ..Default::default()
+        };
+        let parser = TodoParser::from_options(&tags(), options(true)).unwrap();
+
+        assert!(
+            parser
+                .parse_line("See https://example.com/docs#TODO: section", 1)
+                .is_none()
+        );
+        assert!(
+            parser
+                .parse_line("<a href=\"http://x.io/#FIXME: y\">", 1)
+                .is_none()
+        );
+        let real = parser
+            .parse_line("# https://example.com/docs # TODO: update link", 1)
+            .unwrap();
+        assert_eq!(real.message, "update link");
+        let after = parser
+            .parse_line("see https://example.com/#BUG: x then // TODO: real", 1)
+            .unwrap();
+        assert_eq!(after.tag, "TODO");
+
+        let default = TodoParser::from_options(&tags(), options(false)).unwrap();
+        assert!(
+            default
+                .parse_line("See https://example.com/docs#TODO: section", 1)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn crlf_and_bom_never_reach_the_item() {
+        let parser = TodoParser::new(&tags(), true);
+
+        let items = parser.parse_content("\u{feff}// TODO: first\r\nfn a() {}\r\n// FIXME: last\r");
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].message, "first");
+        assert_eq!(items[0].column, 4);
+        assert_eq!(items[0].line_content.as_deref(), Some("// TODO: first"));
+        assert_eq!(items[1].message, "last");
+        assert_eq!(items[1].line_content.as_deref(), Some("// FIXME: last"));
+
+        let buffered = parser
+            .parse_reader("\u{feff}// TODO: piped\r\n".as_bytes())
+            .unwrap();
+        assert_eq!(buffered[0].message, "piped");
+        assert_eq!(buffered[0].column, 4);
+
+        let lexed = parser.parse_lexed("\u{feff}// TODO: lexed\r\n", Language::Rust);
+        assert_eq!(lexed[0].column, 4);
+        assert_eq!(lexed[0].line_content.as_deref(), Some("// TODO: lexed"));
+    }
+
+    #[test]
+    fn columns_count_characters_not_bytes() {
+        let parser = TodoParser::new(&tags(), true);
+
+        let tabbed = parser.parse_line("\t\t// TODO: indented", 1).unwrap();
+        assert_eq!((tabbed.column, tabbed.raw_column), (6, None));
+
+        let line = "é = 1; // TODO: accented";
+        let accented = parser.parse_line(line, 1).unwrap();
+        assert_eq!(accented.column, 11);
+        assert_eq!(line.chars().nth(accented.column - 1), Some('T'));
+
+        let lexed = parser.parse_lexed("let s = \"é\"; // TODO: lexed\n", Language::Rust);
+        assert_eq!(lexed[0].column, 17);
+    }
+
+    #[test]
+    fn context_captures_neighbor_lines_clamped_to_the_file() {
+        let parser = TodoParser::from_options(
+            &tags(),
+            ParserOptions {
+                context_lines: 1,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let items = parser.parse_content(
+            "// TODO: first
+fn a() {}
+// FIXME: middle
+fn b() {}
+// BUG: last",
+        );
+
+        assert_eq!(items.len(), 3);
+        assert!(items[0].context_before.is_empty());
+        assert_eq!(items[0].context_after, ["fn a() {}"]);
+        assert_eq!(items[1].context_before, ["fn a() {}"]);
+        assert_eq!(items[1].context_after, ["fn b() {}"]);
+        assert_eq!(items[2].context_before, ["fn b() {}"]);
+        assert!(items[2].context_after.is_empty());
+
+        let plain = TodoParser::new(&tags(), true).parse_content(
+            "x
+// TODO: none
+y",
+        );
+        assert!(plain[0].context_before.is_empty() && plain[0].context_after.is_empty());
+    }
+
+    #[test]
+    fn lexed_rust_never_reports_tags_in_string_literals() {
+        let parser = TodoParser::from_options(
+            &tags(),
+            ParserOptions {
+                lex_comments: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let source = r##"let a = "TODO: not a comment";
+let b = "still a string
+// TODO: inside a multi-line string
+";
+let c = r#"/* TODO: raw string */"#;
+let d = '"'; // TODO: real one
+/*
+ * FIXME: inside a block
+ */"##;
+
+        let items = parser.parse_lexed(source, Language::Rust);
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].message, "real one");
+        assert_eq!(items[0].line, 6);
+        assert_eq!(items[0].column, 17);
+        assert_eq!(
+            items[0].line_content.as_deref(),
+            Some("let d = '\"'; // TODO: real one")
+        );
+        assert_eq!(items[1].tag, "FIXME");
+        assert_eq!(items[1].line, 8);
+
+        let heuristic = parser.parse_content_with_syntax(source, Some(&CommentSyntax::C_STYLE));
+        assert!(
+            heuristic
+                .iter()
+                .any(|item| item.message == "inside a multi-line string")
+        );
+    }
+
+    #[test]
+    fn parse_reader_parses_piped_content() {
+        let parser = custom_parser(&tags(), false);
+        let input = "fn main() {}\n// TODO: piped in\r\n# FIXME(jane): second\n";
+
+        let items = parser.parse_reader(input.as_bytes()).unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].tag, "TODO");
+        assert_eq!(items[0].message, "piped in");
+        assert_eq!(items[0].line, 2);
+        assert_eq!(items[1].tag, "FIXME");
+        assert_eq!(items[1].author.as_deref(), Some("jane"));
+        assert_eq!(items[1].line, 3);
+    }
+
     #[test]
     fn parse_file_reads_and_parses_content() {
         let parser = custom_parser(&tags(), false);
@@ -294,6 +1242,232 @@ ignore
         assert_eq!(items[1].line, 3);
     }
 
+    #[test]
+    fn comment_syntax_ignores_tags_inside_string_literals() {
+        let parser = TodoParser::new(&tags(), true);
+        let syntax = CommentSyntax::C_STYLE;
+
+        let in_string = r#"let msg = "// TODO: not a real todo";"#;
+        let in_comment = r#"let msg = "hello"; // TODO: not a real todo"#;
+        let both = r#"let msg = "// TODO: fake"; // TODO: real"#;
+
+        assert!(
+            parser.parse_line(in_string, 1).is_some(),
+            "permissive matching still sees the string"
+        );
+        assert!(
+            parser
+                .parse_line_with_syntax(in_string, 1, Some(&syntax))
+                .is_none()
+        );
+
+        let item = parser
+            .parse_line_with_syntax(in_comment, 2, Some(&syntax))
+            .expect("trailing comment should match");
+        assert_eq!(item.message, "not a real todo");
+        assert_eq!(item.column, 23);
+
+        let item = parser
+            .parse_line_with_syntax(both, 3, Some(&syntax))
+            .expect("comment after the string should match");
+        assert_eq!(item.message, "real");
+    }
+
+    #[test]
+    fn parse_file_streams_large_files_and_skips_invalid_utf8() {
+        let parser = TodoParser::new(&tags(), true);
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("todo_parser_large_{unique}.rs"));
+
+        let mut content = Vec::new();
+        content.extend_from_slice(b"// TODO: first\r\n");
+        for i in 0..100_000 {
+            content.extend_from_slice(format!("let x{i} = {i};\n").as_bytes());
+        }
+        content.extend_from_slice(b"// NOTE: \xff\xfe not utf-8\n");
+        content.extend_from_slice(b"// FIXME: near the end\n");
+        fs::write(&path, &content).unwrap();
+
+        let items = parser.parse_file(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].message, "first");
+        assert_eq!(items[1].tag, "FIXME");
+        assert_eq!(items[1].line, 100_003);
+    }
+
+    #[test]
+    fn extensionless_script_uses_its_shebang_comment_syntax() {
+        let parser = TodoParser::new(&tags(), true);
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("todo_parser_shebang_{unique}"));
+        fs::create_dir_all(&dir).unwrap();
+        let body = "x = \"// TODO: inside a string\"\n# TODO: real comment\n";
+        fs::write(
+            dir.join("deploy"),
+            format!("#!/usr/bin/env python3\n{body}"),
+        )
+        .unwrap();
+        fs::write(dir.join("notes"), body).unwrap();
+
+        let script = parser.parse_file(&dir.join("deploy")).unwrap();
+        let plain = parser.parse_file(&dir.join("notes")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(script.len(), 1);
+        assert_eq!(script[0].message, "real comment");
+        assert_eq!(script[0].line, 3);
+        assert_eq!(plain.len(), 2);
+    }
+
+    #[test]
+    fn parse_file_rejects_binary_files() {
+        let parser = TodoParser::new(&tags(), true);
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("todo_parser_binary_{unique}.bin"));
+        fs::write(&path, b"\x00\x01// TODO: not really\n").unwrap();
+
+        let err = parser.parse_file(&path).unwrap_err();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn matches_bare_tags_inside_multiline_block_comments() {
+        let parser = TodoParser::new(&tags(), true);
+        let source =
+            "fn f() {}\n/*\n   TODO: middle of the block\n   and more\n*/\n   TODO: code again";
+
+        let items = parser.parse_content_with_syntax(source, Some(&CommentSyntax::C_STYLE));
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].message, "middle of the block");
+        assert_eq!(items[0].line, 3);
+        assert_eq!(items[0].column, 4);
+        assert_eq!(
+            items[0].line_content.as_deref(),
+            Some("   TODO: middle of the block")
+        );
+    }
+
+    #[test]
+    fn code_after_a_block_close_is_not_a_comment() {
+        let parser = TodoParser::new(&tags(), true);
+        let source = "/*\n * end of docs */ const S: &str = \"# TODO: in a string\";\n/* a\n b */ run(); // FIXME: real";
+
+        let items = parser.parse_content_with_syntax(source, Some(&CommentSyntax::C_STYLE));
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].tag, "FIXME");
+        assert_eq!(items[0].line, 4);
+        assert_eq!(items[0].message, "real");
+    }
+
+    #[test]
+    fn parse_file_uses_comment_syntax_from_extension() {
+        let parser = TodoParser::new(&tags(), true);
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("todo_parser_syntax_{unique}.rs"));
+
+        fs::write(
+            &path,
+            "\
+let a = \"// TODO: inside a string\";
+/*
+ * FIXME: block continuation
+ */
+let b = 1; // BUG: trailing comment",
+        )
+        .unwrap();
+
+        let items = parser.parse_file(&path).unwrap();
+        let permissive = TodoParser::from_options(
+            &tags(),
+            ParserOptions {
+                comments_only: false,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .parse_file(&path)
+        .unwrap();
+        let _ = fs::remove_file(&path);
+
+        let lines: Vec<usize> = items.iter().map(|i| i.line).collect();
+        assert_eq!(lines, vec![3, 5]);
+        assert_eq!(permissive.len(), 3);
+    }
+
+    #[test]
+    fn python_file_only_treats_hash_as_a_comment() {
+        let parser = TodoParser::new(&tags(), true);
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("todo_parser_python_{unique}"));
+        fs::create_dir_all(&dir).unwrap();
+        let content = "# TODO: real comment\nx = 7 // 2  // FIXME: floor division, not a comment\n";
+        fs::write(dir.join("script.py"), content).unwrap();
+        fs::write(dir.join("notes.unknown"), content).unwrap();
+
+        let python = parser.parse_file(&dir.join("script.py")).unwrap();
+        let unknown = parser.parse_file(&dir.join("notes.unknown")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(python.len(), 1);
+        assert_eq!(python[0].tag, "TODO");
+        assert_eq!(unknown.len(), 2);
+    }
+
+    #[test]
+    fn apostrophes_do_not_hide_hash_comments() {
+        let parser = TodoParser::new(&tags(), true);
+
+        let items = parser.parse_content_with_syntax(
+            "it's fine # TODO: after an apostrophe
+x = \"# FIXME: in a string\"",
+            Some(&CommentSyntax::HASH),
+        );
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].message, "after an apostrophe");
+    }
+
+    #[test]
+    fn char_literals_are_skipped_before_c_style_comments() {
+        let parser = TodoParser::new(&tags(), true);
+        let source = "let q = '\"'; // TODO: after a quote char\n\
+                      let s: &'static str = \"x\"; // FIXME: after a lifetime\n\
+                      let e = '\\''; // BUG: after an escaped quote";
+
+        let items = parser.parse_content_with_syntax(source, Some(&CommentSyntax::C_STYLE));
+
+        let messages: Vec<&str> = items.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "after a quote char",
+                "after a lifetime",
+                "after an escaped quote"
+            ]
+        );
+    }
+
     #[test]
     fn require_colon_true_does_not_match_default_pattern_without_colon() {
         let parser = TodoParser::with_options(&tags(), false, true, None);
@@ -329,6 +1503,244 @@ ignore
         assert_eq!(bare_tag.message, "");
     }
 
+    #[test]
+    fn require_colon_false_matches_bare_tags_at_end_of_line_case_sensitively() {
+        let parser = TodoParser::with_options(&tags(), true, false, None);
+
+        for line in ["// TODO", "// TODO ", "// TODO:", "# TODO\t"] {
+            let item = parser
+                .parse_line(line, 1)
+                .unwrap_or_else(|| panic!("{line:?} should match"));
+            assert_eq!(item.tag, "TODO");
+            assert_eq!(item.message, "", "{line:?}");
+        }
+
+        assert!(parser.parse_line("// todo", 1).is_none());
+    }
+
+    #[test]
+    fn allow_empty_matches_bare_tags_with_or_without_colon() {
+        let tags = vec!["DEBUG".to_string(), "FIXME".to_string(), "TODO".to_string()];
+        let strict = TodoParser::new(&tags, true);
+        let allow_empty = TodoParser::from_options(
+            &tags,
+            ParserOptions {
+                allow_empty: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(strict.parse_line("// DEBUG", 1).is_none());
+
+        let item = allow_empty
+            .parse_line("// DEBUG", 1)
+            .expect("bare tag should match with allow_empty");
+        assert_eq!(item.tag, "DEBUG");
+        assert_eq!(item.message, "");
+
+        let bare = allow_empty.parse_line("// FIXME", 2).expect("bare FIXME");
+        let colon = allow_empty
+            .parse_line("// FIXME:", 3)
+            .expect("FIXME with colon");
+        assert_eq!(bare.message, "");
+        assert_eq!(colon.message, "");
+
+        assert!(allow_empty.parse_line("// TODONT", 4).is_none());
+        assert!(allow_empty.parse_line("// TODO later", 5).is_none());
+    }
+
+    #[test]
+    fn extracts_issue_references() {
+        let parser = TodoParser::new(&tags(), true);
+
+        let item = parser.parse_line("// TODO: fix #123 soon", 1).unwrap();
+        assert_eq!(item.issue.as_deref(), Some("#123"));
+        assert_eq!(item.message, "fix #123 soon");
+
+        let item = parser
+            .parse_line("// FIXME: blocked on PROJ-42, then PROJ-43", 2)
+            .unwrap();
+        assert_eq!(item.issue.as_deref(), Some("PROJ-42"));
+
+        let item = parser.parse_line("// FIXME(GH-456): leak", 3).unwrap();
+        assert_eq!(item.issue.as_deref(), Some("GH-456"));
+
+        let item = parser.parse_line("// TODO: nothing to link", 4).unwrap();
+        assert_eq!(item.issue, None);
+    }
+
+    #[test]
+    fn inline_priority_markers_override_the_tag_priority() {
+        let parser = TodoParser::from_options(
+            &["NOTE".to_string(), "TODO".to_string()],
+            ParserOptions {
+                inline_priority: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let note = parser.parse_line("// NOTE [P0]: ship", 1).unwrap();
+        assert_eq!(note.priority, Priority::Critical);
+        assert_eq!(note.message, "ship");
+
+        let leading = parser
+            .parse_line("// TODO(alice): [low] tidy up", 2)
+            .unwrap();
+        assert_eq!(leading.priority, Priority::Low);
+        assert_eq!(leading.message, "tidy up");
+        assert_eq!(leading.author.as_deref(), Some("alice"));
+
+        let unknown = parser.parse_line("// TODO: [WIP] keep", 3).unwrap();
+        assert_eq!(unknown.priority, Priority::Medium);
+        assert_eq!(unknown.message, "[WIP] keep");
+
+        let off = TodoParser::new(&tags(), true);
+        let plain = off.parse_line("// BUG: [P3] still critical", 4).unwrap();
+        assert_eq!(plain.priority, Priority::Critical);
+        assert_eq!(plain.message, "[P3] still critical");
+        assert!(off.parse_line("// TODO [P1]: ship", 5).is_none());
+    }
+
+    #[test]
+    fn dates_in_parentheses_and_due_tokens_become_due_dates() {
+        let parser = TodoParser::new(&tags(), true);
+        let date = |s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok();
+
+        let dated = parser
+            .parse_line("// TODO(2024-12-31): ship it", 1)
+            .unwrap();
+        assert_eq!(dated.due, date("2024-12-31"));
+        assert_eq!(dated.author, None);
+        assert!(dated.authors.is_empty());
+        assert_eq!(dated.message, "ship it");
+
+        let both = parser
+            .parse_line("// TODO(alice, 2025-03-01): migrate", 2)
+            .unwrap();
+        assert_eq!(both.due, date("2025-03-01"));
+        assert_eq!(both.authors, ["alice"]);
+
+        let author = parser.parse_line("// TODO(alice): later", 3).unwrap();
+        assert_eq!(author.due, None);
+        assert_eq!(author.author.as_deref(), Some("alice"));
+
+        let token = parser
+            .parse_line("// FIXME: drop the shim due:2025-06-30", 4)
+            .unwrap();
+        assert_eq!(token.due, date("2025-06-30"));
+
+        let invalid = parser.parse_line("// TODO(2024-13-45): bad", 5).unwrap();
+        assert_eq!(invalid.due, None);
+        assert_eq!(invalid.author.as_deref(), Some("2024-13-45"));
+    }
+
+    #[test]
+    fn configured_tag_priorities_override_defaults() {
+        let path = std::env::temp_dir().join(format!(
+            "todo_parser_priorities_{}.json",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::write(
+            &path,
+            r#"{"tags": ["TODO", "SECURITY"], "tag_priorities": {"security": "Critical"}}"#,
+        )
+        .unwrap();
+        let config = crate::config::Config::load_from_file(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        let parser = TodoParser::from_options(
+            &config.tags,
+            ParserOptions {
+                tag_priorities: config.tag_priorities,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let item = parser.parse_line("// SECURITY: leak", 1).unwrap();
+        assert_eq!(item.priority, Priority::Critical);
+        let item = parser.parse_line("// TODO: unchanged", 2).unwrap();
+        assert_eq!(item.priority, Priority::Medium);
+    }
+
+    #[test]
+    fn config_tag_definitions_add_searched_tags() {
+        let path = std::env::temp_dir().join(format!(
+            "todo_parser_definitions_{}.yaml",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        fs::write(
+            &path,
+            "tags: [TODO]\ntag_definitions:\n  - name: REVIEW\n    description: Needs review\n    priority: High\n",
+        )
+        .unwrap();
+        let config = crate::config::Config::load_from_file(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        let registry = config.tag_registry();
+        let review = registry
+            .find("review")
+            .expect("REVIEW should be registered");
+        assert_eq!(review.priority, Priority::High);
+        assert_eq!(review.description, "Needs review");
+
+        let parser = TodoParser::from_options(
+            &config.scan_tags(),
+            ParserOptions {
+                tag_priorities: config.resolved_tag_priorities(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let item = parser.parse_line("// REVIEW: naming", 1).unwrap();
+        assert_eq!(item.priority, Priority::High);
+    }
+
+    #[test]
+    fn configured_tags_are_trimmed() {
+        let padded = vec!["TODO ".to_string(), " FIXME".to_string(), "  ".to_string()];
+        let parser = TodoParser::new(&padded, false);
+
+        assert_eq!(parser.tags(), ["TODO", "FIXME"]);
+        let item = parser
+            .parse_line("// TODO: padded tag", 1)
+            .expect("padded tag should still match");
+        assert_eq!(item.tag, "TODO");
+        assert!(parser.parse_line("// FIXME: other", 2).is_some());
+    }
+
+    #[test]
+    fn tag_must_end_at_a_word_boundary() {
+        let default = TodoParser::with_options(&tags(), false, false, None);
+        let loose =
+            TodoParser::with_options(&tags(), true, true, Some(r"(?P<tag>$TAGS)(?P<message>.*)$"));
+
+        for parser in [&default, &loose] {
+            assert!(parser.parse_line("// TODOS: foo", 1).is_none());
+            assert!(parser.parse_line("// FIXMEs: foo", 1).is_none());
+        }
+
+        let item = loose
+            .parse_line("TODOS then TODO: real", 2)
+            .expect("later whole-word tag should still match");
+        assert_eq!(item.column, 12);
+        assert_eq!(item.message, ": real");
+
+        let with_author = default
+            .parse_line("// TODO(john): still fine", 3)
+            .expect("author form should match");
+        assert_eq!(with_author.author.as_deref(), Some("john"));
+        assert!(default.parse_line("// TODO: still fine", 4).is_some());
+    }
+
     #[test]
     fn require_colon_false_rejects_false_positives() {
         let parser = TodoParser::with_options(&tags(), false, false, None);
@@ -381,6 +1793,120 @@ ignore
         assert_eq!(item.priority, Priority::from_tag("TODO"));
     }
 
+    #[test]
+    fn multiline_block_keeps_the_tag_line_author() {
+        let parser = TodoParser::from_options(
+            &tags(),
+            ParserOptions {
+                case_sensitive: false,
+                custom_regex: Some(
+                    r"@(?P<tag>$TAGS)\b(?:\((?P<author>[^)]+)\))?\s*(?P<message>.*)$".to_string(),
+                ),
+                multiline: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let items = parser.parse_content("/** @todo(bob) first\n * second */\nfn next() {}");
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].author.as_deref(), Some("bob"));
+        assert_eq!(items[0].message, "first");
+        assert_eq!(items[0].body, vec!["second"]);
+
+        let items = parser.parse_content("/**\n * @todo(bob) first\n * @fixme(eve) second\n */");
+        assert_eq!(items.len(), 2);
+        assert!(items[0].body.is_empty());
+        assert_eq!(items[1].author.as_deref(), Some("eve"));
+    }
+
+    #[test]
+    fn multiline_gathers_following_line_comments() {
+        let parser = TodoParser::from_options(
+            &tags(),
+            ParserOptions {
+                multiline: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let items = parser.parse_content("// TODO: do the thing\n// across two lines\nfn x() {}");
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].message, "do the thing");
+        assert_eq!(items[0].body, vec!["across two lines"]);
+
+        let items = parser.parse_content(
+            "    # TODO: first\n    #   more\n    #\n    # unrelated\n# FIXME: next\n# tail\n",
+        );
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].body, vec!["more"]);
+        assert_eq!(items[1].body, vec!["tail"]);
+
+        let items = parser.parse_content("// TODO: a\n/// docs\n// BUG: b\n");
+        assert_eq!(items.len(), 2);
+        assert!(items[0].body.is_empty());
+
+        let off = custom_parser(&tags(), false).parse_content("// TODO: do the thing\n// across");
+        assert!(off[0].body.is_empty());
+    }
+
+    #[test]
+    fn custom_regex_with_named_groups_matches_at_todo_style() {
+        let parser = TodoParser::from_options(
+            &tags(),
+            ParserOptions {
+                case_sensitive: false,
+                custom_regex: Some(
+                    r"@(?P<tag>$TAGS)\b(?:\((?P<author>[^)]+)\))?\s*(?P<message>.*)$".to_string(),
+                ),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let item = parser
+            .parse_line(" * @todo(alice) document the return value", 4)
+            .expect("expected @todo item");
+
+        assert_eq!(item.tag, "TODO");
+        assert_eq!(item.author.as_deref(), Some("alice"));
+        assert_eq!(item.message, "document the return value");
+        assert_eq!(item.column, 5);
+
+        let item = parser
+            .parse_line("@fixme handle overflow", 5)
+            .expect("expected @fixme item");
+        assert_eq!(item.tag, "FIXME");
+        assert_eq!(item.author, None);
+        assert_eq!(item.message, "handle overflow");
+
+        assert!(parser.parse_line("// TODO: plain style", 6).is_none());
+    }
+
+    #[test]
+    fn invalid_custom_regex_is_an_error() {
+        let invalid = TodoParser::from_options(
+            &tags(),
+            ParserOptions {
+                custom_regex: Some(r"(?P<tag>$TAGS".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(invalid.is_err());
+
+        let no_tag_group = TodoParser::from_options(
+            &tags(),
+            ParserOptions {
+                custom_regex: Some(r"$TAGS:.*".to_string()),
+                ..Default::default()
+            },
+        );
+        let err = no_tag_group.unwrap_err().to_string();
+        assert!(err.contains("`tag` capture group"), "{err}");
+    }
+
     #[test]
     fn default_regex_smoke_test_common_comment_styles() {
         let parser = TodoParser::with_options(&tags(), false, true, None);
@@ -407,4 +1933,48 @@ ignore
 
         assert_eq!(parser.tags(), &tags);
     }
+
+    #[test]
+    fn many_tags_match_like_a_few_and_stay_fast() {
+        let mut many: Vec<String> = (0..500).map(|i| format!("TAG{:03}X", i)).collect();
+        many.extend(tags());
+        let content: String = (0..2_000)
+            .map(|i| match i % 50 {
+                0 => format!("// TAG{:03}X: generated {}\n", i % 500, i),
+                1 => format!("// todo(bob): lower case {}\n", i),
+                2 => "// TAG1000X: not a configured tag\n".to_string(),
+                _ => format!("let value_{} = compute({});\n", i, i),
+            })
+            .collect();
+
+        let prefiltered = TodoParser::new(&many, false);
+        assert!(prefiltered.prefilter.is_some());
+        let plain = TodoParser {
+            prefilter: None,
+            ..prefiltered.clone()
+        };
+
+        let start = std::time::Instant::now();
+        let items = prefiltered.parse_content(&content);
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+
+        assert_eq!(items, plain.parse_content(&content));
+        assert_eq!(items.len(), 80);
+        assert_eq!(items[0].tag, "TAG000X");
+        assert_eq!(items[1].tag, "TODO");
+        assert_eq!(items[1].author.as_deref(), Some("bob"));
+        assert!(items.iter().all(|item| item.tag != "TAG1000X"));
+    }
+
+    #[test]
+    fn default_options_match_the_config_default_case_sensitivity() {
+        let config = crate::config::Config::default();
+        let options = ParserOptions::default();
+        assert_eq!(options.case_sensitive, !config.ignore_case);
+
+        let parser = TodoParser::from_options(&tags(), options).unwrap();
+        assert!(parser.parse_line("// TODO: found", 1).is_some());
+        assert!(parser.parse_line("// todo: not found", 1).is_none());
+        assert!(parser.parse_line("// Todo: not found", 1).is_none());
+    }
 }
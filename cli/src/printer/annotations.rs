@@ -0,0 +1,149 @@
+use super::options::PrintOptions;
+use super::utils::format_path;
+use anyhow::{Context, Result, bail};
+use std::io::{self, Write};
+use std::path::Path;
+use todo_tree_core::{Priority, ScanResult, TodoItem};
+
+/// First line of every annotations file, so readers can reject other formats
+/// and future versions.
+pub const HEADER: &str = "todo-tree-annotations 1";
+
+/// Compact per-file listing for editor plugins to load without rescanning.
+/// Each file path sits on its own line, followed by one tab-indented
+/// `line<TAB>column<TAB>tag<TAB>message` line per item.
+pub fn print_annotations<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    writeln!(writer, "{}", HEADER)?;
+
+    for (path, items) in result.sorted_files() {
+        writeln!(writer, "{}", sanitize_field(&format_path(path, options)))?;
+        for item in items {
+            writeln!(
+                writer,
+                "\t{}\t{}\t{}\t{}",
+                item.line,
+                item.column,
+                sanitize_field(&item.tag),
+                sanitize_field(&item.message)
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Read an annotations file back into a [`ScanResult`], resolving relative
+/// paths against `root`. Only the fields the format stores are filled in.
+pub fn read_annotations(text: &str, root: &Path) -> Result<ScanResult> {
+    let mut lines = text.lines().enumerate();
+    match lines.next() {
+        Some((_, HEADER)) => {}
+        _ => bail!("Not a todo-tree annotations file: expected `{}`", HEADER),
+    }
+
+    let mut files: Vec<(String, Vec<TodoItem>)> = Vec::new();
+    for (idx, line) in lines {
+        let Some(fields) = line.strip_prefix('\t') else {
+            files.push((line.to_string(), Vec::new()));
+            continue;
+        };
+        let Some((_, items)) = files.last_mut() else {
+            bail!("Line {}: annotation before any file path", idx + 1);
+        };
+        items.push(parse_item(fields).with_context(|| format!("Line {}", idx + 1))?);
+    }
+
+    let mut result = ScanResult::new(root.to_path_buf());
+    for (path, items) in files {
+        result.add_file(root.join(path), items);
+    }
+    Ok(result)
+}
+
+fn parse_item(fields: &str) -> Result<TodoItem> {
+    let mut fields = fields.splitn(4, '\t');
+    let mut next = |name: &str| {
+        fields
+            .next()
+            .with_context(|| format!("missing {} field", name))
+    };
+    let line = next("line")?.parse().context("invalid line number")?;
+    let column = next("column")?.parse().context("invalid column")?;
+    let tag = next("tag")?.to_string();
+    let message = next("message")?.to_string();
+
+    Ok(TodoItem {
+        priority: Priority::from_tag(&tag),
+        tag,
+        message,
+        line,
+        column,
+        ..Default::default()
+    })
+}
+
+/// Tabs and line breaks would split a record, so they become spaces.
+fn sanitize_field(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::test_support::{item_at, render, result_in};
+
+    #[test]
+    fn round_trips_into_a_scan_result() {
+        let result = result_in(
+            "/repo",
+            vec![
+                (
+                    "src/lib.rs",
+                    vec![
+                        item_at("TODO", "share me", 3, 4),
+                        item_at("FIXME", "tabs\tsplit", 9, 1),
+                    ],
+                ),
+                ("README.md", vec![item_at("NOTE", "", 1, 5)]),
+            ],
+        );
+
+        let text = render(print_annotations, &result);
+        assert_eq!(
+            text,
+            "todo-tree-annotations 1\n\
+             README.md\n\
+             \t1\t5\tNOTE\t\n\
+             src/lib.rs\n\
+             \t3\t4\tTODO\tshare me\n\
+             \t9\t1\tFIXME\ttabs split\n"
+        );
+
+        let read = read_annotations(&text, Path::new("/repo")).unwrap();
+        assert_eq!(read.summary.total_count, result.summary.total_count);
+        assert_eq!(read.summary.files_with_todos, 2);
+        assert_eq!(read.summary.tag_counts, result.summary.tag_counts);
+        let mut expected = result.sorted_items(Default::default());
+        expected[2].1.message = "tabs split".to_string();
+        assert_eq!(read.sorted_items(Default::default()), expected);
+    }
+
+    #[test]
+    fn rejects_other_formats_and_orphan_items() {
+        assert!(read_annotations("file\tline\n", Path::new("/repo")).is_err());
+        assert!(
+            read_annotations("todo-tree-annotations 1\n\t1\t1\tTODO\tx\n", Path::new("/")).is_err()
+        );
+        assert!(
+            read_annotations(
+                "todo-tree-annotations 1\na.rs\n\tone\t1\tTODO\tx\n",
+                Path::new("/")
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use super::options::PrintOptions;
+use super::utils::{format_path, terminal_safe};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::Path;
+use todo_tree_core::{ScanResult, TodoItem};
+
+/// A directory or file in the path tree built from a scan.
+#[derive(Debug, Default)]
+pub(super) struct PathNode<'a> {
+    pub(super) children: BTreeMap<String, PathNode<'a>>,
+    pub(super) items: Vec<&'a TodoItem>,
+    /// The scanned path, set on file nodes.
+    pub(super) path: Option<&'a Path>,
+}
+
+impl<'a> PathNode<'a> {
+    pub(super) fn build(result: &'a ScanResult, options: &PrintOptions) -> Self {
+        let mut root = PathNode::default();
+
+        for (path, items) in result.sorted_files() {
+            let display_path = format_path(path, options);
+            let mut node = &mut root;
+            for part in display_path.split(['/', '\\']).filter(|p| !p.is_empty()) {
+                node = node.children.entry(part.to_string()).or_default();
+            }
+            node.items.extend(items);
+            node.path = Some(path.as_path());
+        }
+
+        root
+    }
+
+    /// Items in this node and everything below it, summed bottom-up.
+    pub(super) fn count(&self) -> usize {
+        self.items.len() + self.children.values().map(PathNode::count).sum::<usize>()
+    }
+
+    pub(super) fn is_dir(&self) -> bool {
+        !self.children.is_empty()
+    }
+}
+
+/// Print matches under a plain-ASCII directory tree, optionally with the
+/// number of items in each subtree.
+pub fn print_ascii_tree<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    if result.is_empty() {
+        writeln!(writer, "No TODO items found.")?;
+        return Ok(());
+    }
+
+    let root = PathNode::build(result, options);
+    print_children(writer, &root, "", options)
+}
+
+fn print_children<W: Write>(
+    writer: &mut W,
+    node: &PathNode,
+    prefix: &str,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    let total = node.children.len();
+
+    for (idx, (name, child)) in node.children.iter().enumerate() {
+        let is_last = idx == total - 1;
+        let (branch, indent) = if is_last {
+            ("`-- ", "    ")
+        } else {
+            ("|-- ", "|   ")
+        };
+
+        let label = if child.is_dir() {
+            format!("{}/", name)
+        } else {
+            name.clone()
+        };
+        if options.show_counts {
+            writeln!(writer, "{}{}{} ({})", prefix, branch, label, child.count())?;
+        } else {
+            writeln!(writer, "{}{}{}", prefix, branch, label)?;
+        }
+
+        let child_prefix = format!("{}{}", prefix, indent);
+        print_children(writer, child, &child_prefix, options)?;
+
+        let item_total = child.items.len();
+        for (item_idx, item) in child.items.iter().enumerate() {
+            let item_branch = if item_idx == item_total - 1 {
+                "`-- "
+            } else {
+                "|-- "
+            };
+            writeln!(
+                writer,
+                "{}{}L{} [{}] {}",
+                child_prefix,
+                item_branch,
+                item.line,
+                item.tag,
+                terminal_safe(&item.message)
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn items(count: usize) -> Vec<TodoItem> {
+        (1..=count)
+            .map(|line| TodoItem {
+                tag: "TODO".to_string(),
+                message: format!("item {}", line),
+                line,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn result() -> ScanResult {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(PathBuf::from("/repo/src/main.rs"), items(2));
+        result.add_file(PathBuf::from("/repo/src/cli/args.rs"), items(3));
+        result.add_file(PathBuf::from("/repo/README.md"), items(1));
+        result
+    }
+
+    fn options(show_counts: bool) -> PrintOptions {
+        PrintOptions {
+            base_path: Some(PathBuf::from("/repo")),
+            show_counts,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn directory_count_is_the_sum_of_its_children() {
+        let result = result();
+        let options = options(true);
+        let root = PathNode::build(&result, &options);
+
+        let src = &root.children["src"];
+        let children_total: usize = src.children.values().map(PathNode::count).sum();
+        assert_eq!(src.count(), children_total);
+        assert_eq!(src.count(), 5);
+        assert_eq!(root.count(), result.summary.total_count);
+    }
+
+    #[test]
+    fn renders_counts_only_when_enabled() {
+        let result = result();
+
+        let mut out = Vec::new();
+        print_ascii_tree(&mut out, &result, &options(true)).unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert_eq!(
+            output,
+            "|-- README.md (1)\n\
+             |   `-- L1 [TODO] item 1\n\
+             `-- src/ (5)\n    \
+             |-- cli/ (3)\n    \
+             |   `-- args.rs (3)\n    \
+             |       |-- L1 [TODO] item 1\n    \
+             |       |-- L2 [TODO] item 2\n    \
+             |       `-- L3 [TODO] item 3\n    \
+             `-- main.rs (2)\n        \
+             |-- L1 [TODO] item 1\n        \
+             `-- L2 [TODO] item 2\n"
+        );
+
+        let mut out = Vec::new();
+        print_ascii_tree(&mut out, &result, &options(false)).unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.starts_with("|-- README.md\n"));
+        assert!(output.contains("`-- src/\n"));
+    }
+}
@@ -0,0 +1,132 @@
+use super::flat::print_flat;
+use super::options::PrintOptions;
+use std::io::{self, Write};
+use todo_tree_core::ScanResult;
+
+/// Somewhere to put copied text; the system clipboard or a test double.
+pub trait Clipboard {
+    fn set_text(&mut self, text: String) -> Result<(), String>;
+}
+
+#[cfg(feature = "clipboard")]
+struct SystemClipboard(arboard::Clipboard);
+
+#[cfg(feature = "clipboard")]
+impl Clipboard for SystemClipboard {
+    fn set_text(&mut self, text: String) -> Result<(), String> {
+        self.0.set_text(text).map_err(|err| err.to_string())
+    }
+}
+
+/// The OS clipboard, or `None` when there is none (e.g. headless CI) or the
+/// binary was built without the `clipboard` feature.
+#[cfg(feature = "clipboard")]
+pub fn system_clipboard() -> Option<Box<dyn Clipboard>> {
+    arboard::Clipboard::new()
+        .ok()
+        .map(|clipboard| Box::new(SystemClipboard(clipboard)) as Box<dyn Clipboard>)
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn system_clipboard() -> Option<Box<dyn Clipboard>> {
+    None
+}
+
+/// Copy the plain flat list to `clipboard`, falling back to writing it to
+/// `writer` with a warning on stderr when copying is not possible.
+pub fn print_clipboard<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+    clipboard: Option<Box<dyn Clipboard>>,
+) -> io::Result<()> {
+    let plain = PrintOptions {
+        colored: false,
+        clickable_links: false,
+        ..options.clone()
+    };
+    let mut text = Vec::new();
+    print_flat(&mut text, result, &plain)?;
+    let text = String::from_utf8_lossy(&text).into_owned();
+
+    let copied = match clipboard {
+        Some(mut clipboard) => clipboard.set_text(text.clone()),
+        None => Err("no clipboard available".to_string()),
+    };
+
+    match copied {
+        Ok(()) => eprintln!(
+            "Copied {} TODO items to the clipboard",
+            result.summary.total_count
+        ),
+        Err(reason) => {
+            eprintln!("Warning: {}; printing to stdout instead", reason);
+            writer.write_all(text.as_bytes())?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use todo_tree_core::TodoItem;
+
+    struct MockClipboard(Arc<Mutex<Option<String>>>);
+
+    impl Clipboard for MockClipboard {
+        fn set_text(&mut self, text: String) -> Result<(), String> {
+            *self.0.lock().unwrap() = Some(text);
+            Ok(())
+        }
+    }
+
+    fn result() -> ScanResult {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        let item = TodoItem {
+            tag: "TODO".to_string(),
+            message: "share me".to_string(),
+            line: 3,
+            column: 4,
+            ..Default::default()
+        };
+        result.add_file(PathBuf::from("/repo/src/lib.rs"), vec![item]);
+        result
+    }
+
+    fn options() -> PrintOptions {
+        PrintOptions {
+            base_path: Some(PathBuf::from("/repo")),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn copies_plain_text_instead_of_printing() {
+        let copied = Arc::new(Mutex::new(None));
+        let clipboard = Box::new(MockClipboard(Arc::clone(&copied)));
+
+        let mut out = Vec::new();
+        print_clipboard(&mut out, &result(), &options(), Some(clipboard)).unwrap();
+
+        assert!(out.is_empty());
+        assert_eq!(
+            copied.lock().unwrap().as_deref(),
+            Some("src/lib.rs TODO[Medium] 3:4: share me\n")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_writer_without_a_clipboard() {
+        let mut out = Vec::new();
+        print_clipboard(&mut out, &result(), &options(), None).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "src/lib.rs TODO[Medium] 3:4: share me\n"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use super::options::PrintOptions;
+use super::utils::{format_path, to_posix_separators};
+use serde::Serialize;
+use std::io::{self, Write};
+use todo_tree_core::hash::fnv1a_64;
+use todo_tree_core::{Priority, ScanResult, TodoItem};
+
+/// One entry of a GitLab Code Quality report.
+#[derive(Debug, Serialize)]
+pub struct CodeQualityIssue {
+    pub description: String,
+    pub check_name: String,
+    pub fingerprint: String,
+    pub severity: &'static str,
+    pub location: CodeQualityLocation,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CodeQualityLocation {
+    pub path: String,
+    pub lines: CodeQualityLines,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CodeQualityLines {
+    pub begin: usize,
+}
+
+/// JSON array GitLab CI renders on merge requests when uploaded as a
+/// `codequality` report artifact.
+pub fn print_codequality<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    let issues: Vec<CodeQualityIssue> = result
+        .sorted_files()
+        .into_iter()
+        .flat_map(|(path, items)| {
+            let path = to_posix_separators(&format_path(path, options));
+            items
+                .iter()
+                .map(move |item| CodeQualityIssue::new(&path, item))
+        })
+        .collect();
+    let json_str = serde_json::to_string_pretty(&issues).map_err(io::Error::other)?;
+    writeln!(writer, "{}", json_str)?;
+    Ok(())
+}
+
+impl CodeQualityIssue {
+    fn new(path: &str, item: &TodoItem) -> Self {
+        Self {
+            description: format!("{}: {}", item.tag, item.message),
+            check_name: item.tag.clone(),
+            fingerprint: fingerprint(path, item),
+            severity: codequality_severity(item.priority),
+            location: CodeQualityLocation {
+                path: path.to_string(),
+                lines: CodeQualityLines { begin: item.line },
+            },
+        }
+    }
+}
+
+/// GitLab matches issues between pipelines by fingerprint, so it depends only
+/// on the relative path, line and message.
+fn fingerprint(path: &str, item: &TodoItem) -> String {
+    let key = format!("{}\0{}\0{}", path, item.line, item.message);
+    format!("{:016x}", fnv1a_64(key.as_bytes()))
+}
+
+fn codequality_severity(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Critical => "critical",
+        Priority::High => "major",
+        Priority::Medium => "minor",
+        Priority::Low => "info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::test_support::{item, render, result_in};
+
+    fn report(root: &str, files: Vec<(&str, Vec<TodoItem>)>) -> serde_json::Value {
+        let output = render(print_codequality, &result_in(root, files));
+        serde_json::from_str(&output).unwrap()
+    }
+
+    #[test]
+    fn emits_one_issue_per_item_with_location_and_severity() {
+        let report = report(
+            "/repo",
+            vec![(
+                "src/lib.rs",
+                vec![item("TODO", "tidy up", 3), item("BUG", "off by one", 9)],
+            )],
+        );
+
+        let issues = report.as_array().unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0]["description"], "TODO: tidy up");
+        assert_eq!(issues[0]["check_name"], "TODO");
+        assert_eq!(issues[0]["location"]["path"], "src/lib.rs");
+        assert_eq!(issues[0]["location"]["lines"]["begin"], 3);
+        assert_eq!(issues[0]["severity"], "minor");
+        assert_eq!(issues[1]["severity"], "critical");
+    }
+
+    #[test]
+    fn fingerprint_is_stable_across_checkouts() {
+        let files = || vec![("src/lib.rs", vec![item("TODO", "tidy up", 3)])];
+        let first = report("/repo", files());
+        let second = report("/elsewhere/repo", files());
+        let moved = report(
+            "/repo",
+            vec![("src/lib.rs", vec![item("TODO", "tidy up", 4)])],
+        );
+
+        assert_eq!(first[0]["fingerprint"], second[0]["fingerprint"]);
+        assert_eq!(first[0]["fingerprint"].as_str().unwrap().len(), 16);
+        assert_ne!(first[0]["fingerprint"], moved[0]["fingerprint"]);
+    }
+
+    #[test]
+    fn severity_follows_priority() {
+        assert_eq!(codequality_severity(Priority::Critical), "critical");
+        assert_eq!(codequality_severity(Priority::High), "major");
+        assert_eq!(codequality_severity(Priority::Medium), "minor");
+        assert_eq!(codequality_severity(Priority::Low), "info");
+    }
+}
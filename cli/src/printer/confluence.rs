@@ -0,0 +1,76 @@
+use super::options::PrintOptions;
+use super::utils::format_path;
+use std::io::{self, Write};
+use todo_tree_core::ScanResult;
+
+const HEADER: &str = "||Priority||Tag||Location||Message||";
+
+pub fn print_confluence<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    writeln!(writer, "{}", HEADER)?;
+
+    let mut all_items = result.all_items();
+    all_items.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.line.cmp(&b.1.line)));
+
+    for (path, item) in all_items {
+        let location = format!("{}:{}", format_path(&path, options), item.line);
+        writeln!(
+            writer,
+            "|{}|{}|{}|{}|",
+            item.priority,
+            escape_cell(&item.tag),
+            escape_cell(&location),
+            escape_cell(&item.message)
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Escape a value for a wiki markup table cell. Empty cells need a space or
+/// Confluence collapses the column.
+fn escape_cell(value: &str) -> String {
+    if value.is_empty() {
+        return " ".to_string();
+    }
+
+    value.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use todo_tree_core::{Priority, TodoItem};
+
+    #[test]
+    fn renders_header_and_escapes_pipes_in_messages() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(
+            PathBuf::from("/repo/src/lib.rs"),
+            vec![TodoItem {
+                tag: "HACK".to_string(),
+                message: "use a | b instead".to_string(),
+                line: 12,
+                column: 4,
+                priority: Priority::High,
+                ..Default::default()
+            }],
+        );
+
+        let options = PrintOptions {
+            base_path: Some(PathBuf::from("/repo")),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        print_confluence(&mut out, &result, &options).unwrap();
+        let output = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[0], "||Priority||Tag||Location||Message||");
+        assert_eq!(lines[1], "|High|HACK|src/lib.rs:12|use a \\| b instead|");
+    }
+}
@@ -0,0 +1,104 @@
+use super::options::PrintOptions;
+use super::utils::format_path;
+use std::io::{self, Write};
+use todo_tree_core::ScanResult;
+
+const HEADER: &str = "file,line,column,tag,priority,author,message";
+
+/// One RFC 4180 row per item, for importing into a spreadsheet.
+pub fn print_csv<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    writeln!(writer, "{}", HEADER)?;
+
+    for (path, item) in result.sorted_items(Default::default()) {
+        let fields = [
+            quote_field(&format_path(&path, options)),
+            item.line.to_string(),
+            item.column.to_string(),
+            quote_field(&item.tag),
+            item.priority.to_string(),
+            quote_field(&item.authors.join(",")),
+            quote_field(&item.message),
+        ];
+        writeln!(writer, "{}", fields.join(","))?;
+    }
+
+    Ok(())
+}
+
+/// Quote a field containing a separator, quote or line break, doubling any
+/// quotes inside it.
+fn quote_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use todo_tree_core::{Priority, TodoItem};
+
+    #[test]
+    fn round_trips_messages_with_commas_and_quotes() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(
+            PathBuf::from("/repo/src/lib.rs"),
+            vec![
+                TodoItem {
+                    tag: "TODO".to_string(),
+                    message: "split on \"a, b\", then join".to_string(),
+                    line: 3,
+                    column: 4,
+                    authors: vec!["alice".to_string(), "bob".to_string()],
+                    priority: Priority::Medium,
+                    ..Default::default()
+                },
+                TodoItem {
+                    tag: "BUG".to_string(),
+                    message: "plain".to_string(),
+                    line: 9,
+                    column: 1,
+                    priority: Priority::Critical,
+                    ..Default::default()
+                },
+            ],
+        );
+        let options = PrintOptions {
+            base_path: Some(PathBuf::from("/repo")),
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        print_csv(&mut out, &result, &options).unwrap();
+
+        let mut reader = ::csv::Reader::from_reader(out.as_slice());
+        assert_eq!(
+            reader.headers().unwrap(),
+            vec![
+                "file", "line", "column", "tag", "priority", "author", "message"
+            ]
+        );
+        let rows: Vec<::csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0],
+            vec![
+                "src/lib.rs",
+                "3",
+                "4",
+                "TODO",
+                "Medium",
+                "alice,bob",
+                "split on \"a, b\", then join"
+            ]
+        );
+        assert_eq!(&rows[1][6], "plain");
+    }
+}
@@ -0,0 +1,94 @@
+use super::options::PrintOptions;
+use super::utils::format_path;
+use std::io::{self, Write};
+use todo_tree_core::ScanResult;
+
+/// Describe every match with its raw line and captured groups, for tracking
+/// down false positives. Written to stderr by `--print-matched-tags`, from the
+/// scan before any filtering, so matches that are later dropped show up too.
+pub fn print_matched_tags<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    for (path, item) in result.sorted_items(Default::default()) {
+        let written = item.matched_tag.as_deref().unwrap_or(&item.tag);
+        writeln!(
+            writer,
+            "{}:{}:{}: matched {}",
+            format_path(&path, options),
+            item.line,
+            item.column,
+            written
+        )?;
+        writeln!(
+            writer,
+            "  line:    {}",
+            item.line_content.as_deref().unwrap_or_default()
+        )?;
+        writeln!(writer, "  tag:     {:?}", written)?;
+        if item.matched_tag.is_some() {
+            writeln!(writer, "  as:      {:?}", item.tag)?;
+        }
+        if !item.authors.is_empty() {
+            writeln!(writer, "  author:  {:?}", item.authors.join(", "))?;
+        }
+        writeln!(writer, "  message: {:?}", item.message)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TodoParser;
+    use std::path::PathBuf;
+
+    #[test]
+    fn shows_raw_line_tag_and_captures() {
+        let parser = TodoParser::new(&["TODO".to_string()], true);
+        let line = "let s = 1; // TODO(alice): check bounds";
+        let item = parser.parse_line(line, 7).unwrap();
+
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(PathBuf::from("/repo/src/lib.rs"), vec![item]);
+        let options = PrintOptions {
+            base_path: Some(PathBuf::from("/repo")),
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        print_matched_tags(&mut out, &result, &options).unwrap();
+        let output = String::from_utf8(out).unwrap();
+
+        assert_eq!(
+            output,
+            "src/lib.rs:7:15: matched TODO\n\
+             \x20 line:    let s = 1; // TODO(alice): check bounds\n\
+             \x20 tag:     \"TODO\"\n\
+             \x20 author:  \"alice\"\n\
+             \x20 message: \"check bounds\"\n"
+        );
+    }
+
+    #[test]
+    fn shows_the_tag_as_written_when_case_was_folded() {
+        let parser = TodoParser::new(&["TODO".to_string()], false);
+        let item = parser.parse_line("# todo: lower case", 2).unwrap();
+
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(PathBuf::from("/repo/run.sh"), vec![item]);
+        let options = PrintOptions {
+            base_path: Some(PathBuf::from("/repo")),
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        print_matched_tags(&mut out, &result, &options).unwrap();
+        let output = String::from_utf8(out).unwrap();
+
+        assert!(output.starts_with("run.sh:2:3: matched todo\n"), "{output}");
+        assert!(output.contains("  tag:     \"todo\"\n  as:      \"TODO\"\n"));
+    }
+}
@@ -1,9 +1,13 @@
 use super::options::PrintOptions;
-use super::utils::{colorize_tag, format_path, make_clickable_link};
+use super::utils::{
+    blame_suffix, colorize_tag, description_suffix, duplicates_suffix, format_path,
+    make_clickable_link, split_head_tail, terminal_safe,
+};
 use colored::Colorize;
+use std::collections::HashSet;
 use std::io::{self, Write};
 use std::path::Path;
-use todo_tree_core::{ScanResult, TodoItem};
+use todo_tree_core::{ScanResult, SortKey, TodoItem};
 
 pub fn print_flat<W: Write>(
     writer: &mut W,
@@ -15,16 +19,62 @@ pub fn print_flat<W: Write>(
         return Ok(());
     }
 
-    let mut all_items = result.all_items();
-    all_items.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.line.cmp(&b.1.line)));
+    if options.sort != SortKey::Path {
+        let (kept, elided) = head_tail_per_file(result, options);
+        for (path, item) in result.sorted_items(options.sort) {
+            if kept.contains(&(path.as_path(), item.line, item.column)) {
+                print_flat_item(writer, &path, &item, options)?;
+            }
+        }
+        if elided > 0 {
+            writeln!(writer, "{}", format!("… {} more", elided).dimmed())?;
+        }
+        return Ok(());
+    }
+
+    for (path, items) in result.sorted_files() {
+        let mut items: Vec<&TodoItem> = items.iter().collect();
+        items.sort_by_key(|item| item.line);
 
-    for (path, item) in all_items {
-        print_flat_item(writer, &path, &item, options)?;
+        let (head, elided, tail) = split_head_tail(&items, options.head, options.tail);
+        for item in head {
+            print_flat_item(writer, path, item, options)?;
+        }
+        if elided > 0 {
+            let note = format!("… {} more in {}", elided, format_path(path, options));
+            writeln!(writer, "{}", note.dimmed())?;
+        }
+        for item in tail {
+            print_flat_item(writer, path, item, options)?;
+        }
     }
 
     Ok(())
 }
 
+/// The items `--head`/`--tail` keep from each file, by path, line and
+/// column, and how many they elide in total.
+fn head_tail_per_file<'a>(
+    result: &'a ScanResult,
+    options: &PrintOptions,
+) -> (HashSet<(&'a Path, usize, usize)>, usize) {
+    let mut kept = HashSet::new();
+    let mut elided = 0;
+    for (path, items) in &result.files_map {
+        let mut items: Vec<&TodoItem> = items.iter().collect();
+        items.sort_by_key(|item| (item.line, item.column));
+
+        let (head, skipped, tail) = split_head_tail(&items, options.head, options.tail);
+        kept.extend(
+            head.iter()
+                .chain(tail)
+                .map(|item| (path.as_path(), item.line, item.column)),
+        );
+        elided += skipped;
+    }
+    (kept, elided)
+}
+
 fn print_flat_item<W: Write>(
     writer: &mut W,
     path: &Path,
@@ -42,19 +92,109 @@ fn print_flat_item<W: Write>(
         }
     });
 
-    let line_col = format!(":{}:{}", item.line, item.column);
-    let line_col_display = if options.colored {
-        line_col.cyan().to_string()
+    let message = terminal_safe(&item.message);
+    // The item's own Display line, with colors layered on when enabled.
+    let text = if options.colored {
+        let mut text = format!(
+            "{}{}[{}] {}",
+            colorize_tag(&item.tag, options),
+            item.format_author(),
+            item.priority,
+            format!("{}:{}:", item.line, item.column).cyan()
+        );
+        if !message.is_empty() {
+            text.push_str(&format!(" {}", message));
+        }
+        text
     } else {
-        line_col
+        TodoItem {
+            message: message.into_owned(),
+            ..item.clone()
+        }
+        .to_string()
     };
 
-    let tag = colorize_tag(&item.tag, options);
-
     writeln!(
         writer,
-        "{}{} [{}] {}",
-        path_str, line_col_display, tag, item.message
+        "{} {}{}{}{}",
+        path_str,
+        text,
+        description_suffix(&item.tag, options),
+        duplicates_suffix(path, item, options),
+        blame_suffix(item, options)
     )?;
+    print_context(writer, item, options)
+}
+
+/// Numbered source lines around the item, captured with `--context`.
+fn print_context<W: Write>(
+    writer: &mut W,
+    item: &TodoItem,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    if item.context_before.is_empty() && item.context_after.is_empty() {
+        return Ok(());
+    }
+
+    let first = item.line - item.context_before.len();
+    let last = item.line + item.context_after.len();
+    let width = last.to_string().len();
+    let lines = item
+        .context_before
+        .iter()
+        .map(String::as_str)
+        .chain([item.line_content.as_deref().unwrap_or_default()])
+        .chain(item.context_after.iter().map(String::as_str));
+
+    for (number, text) in (first..=last).zip(lines) {
+        let marker = if number == item.line { '>' } else { ' ' };
+        let line = format!("  {} {:>width$} | {}", marker, number, terminal_safe(text));
+        if options.colored && number != item.line {
+            writeln!(writer, "{}", line.dimmed())?;
+        } else {
+            writeln!(writer, "{}", line)?;
+        }
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::test_support::{item, options_in, result_in};
+
+    #[test]
+    fn head_applies_to_each_file_before_sorting() {
+        let result = result_in(
+            "/repo",
+            vec![
+                (
+                    "a.rs",
+                    vec![item("TODO", "a first", 1), item("BUG", "a second", 2)],
+                ),
+                (
+                    "b.rs",
+                    vec![item("BUG", "b first", 1), item("TODO", "b second", 2)],
+                ),
+            ],
+        );
+        let options = PrintOptions {
+            sort: SortKey::Priority,
+            head: Some(1),
+            colored: false,
+            clickable_links: false,
+            ..options_in("/repo")
+        };
+
+        let mut out = Vec::new();
+        print_flat(&mut out, &result, &options).unwrap();
+        let output = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 3, "{}", output);
+        assert!(lines[0].contains("b first"), "{}", output);
+        assert!(lines[1].contains("a first"), "{}", output);
+        assert!(lines[2].contains("… 2 more"), "{}", output);
+    }
+}
@@ -0,0 +1,138 @@
+use super::options::PrintOptions;
+use super::summary::print_count;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use todo_tree_core::ScanResult;
+
+/// Notes ref the summaries are written to, kept apart from `refs/notes/commits`
+/// so they never show up in a plain `git log`.
+pub const NOTES_REF: &str = "refs/notes/todo-tree";
+
+/// Attach the `--count` summary of `result` to `HEAD` of the repository at
+/// the scan root as a git note, replacing any earlier one, and echo it to
+/// `writer`. Read it back with `git notes --ref todo-tree show`.
+pub fn print_git_notes<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    let mut note = Vec::new();
+    print_count(&mut note, &result.summary)?;
+
+    let repo = options
+        .base_path
+        .as_deref()
+        .or(result.root.as_deref())
+        .unwrap_or(Path::new("."));
+    add_note(repo, &note)?;
+    eprintln!("Wrote the summary to HEAD's note in {}", NOTES_REF);
+
+    writer.write_all(&note)
+}
+
+fn add_note(repo: &Path, note: &[u8]) -> io::Result<()> {
+    let mut child = Command::new("git")
+        .current_dir(repo)
+        .args([
+            "notes", "--ref", NOTES_REF, "add", "--force", "--file", "-", "HEAD",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(note)?;
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "git notes failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use todo_tree_core::TodoItem;
+
+    fn git(dir: &Path, args: &[&str]) -> Option<String> {
+        let output = Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    #[test]
+    fn writes_a_readable_note_on_head() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let repo = std::env::temp_dir().join(format!("todo_notes_repo_{unique}"));
+        fs::create_dir_all(&repo).unwrap();
+        fs::write(repo.join("lib.rs"), "// TODO: note me\n").unwrap();
+        let ready = [
+            &["init", "-q"][..],
+            &["config", "user.name", "Ada"],
+            &["config", "user.email", "ada@example.com"],
+            &["add", "lib.rs"],
+            &["commit", "-q", "--no-gpg-sign", "-m", "init"],
+        ]
+        .iter()
+        .all(|args| git(&repo, args).is_some());
+        if !ready {
+            // No usable git here; nothing to check.
+            let _ = fs::remove_dir_all(&repo);
+            return;
+        }
+
+        let mut result = ScanResult::new(repo.clone());
+        let item = TodoItem {
+            tag: "TODO".to_string(),
+            message: "note me".to_string(),
+            line: 1,
+            column: 4,
+            ..Default::default()
+        };
+        result.add_file(repo.join("lib.rs"), vec![item]);
+        let options = PrintOptions {
+            base_path: Some(repo.clone()),
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        print_git_notes(&mut out, &result, &options).unwrap();
+        // A second run replaces the note instead of failing.
+        print_git_notes(&mut Vec::new(), &result, &options).unwrap();
+        let note = git(&repo, &["notes", "--ref", NOTES_REF, "show", "HEAD"]);
+        let outside = print_git_notes(
+            &mut Vec::new(),
+            &result,
+            &PrintOptions {
+                base_path: Some(PathBuf::from("/")),
+                ..Default::default()
+            },
+        );
+        let _ = fs::remove_dir_all(&repo);
+
+        let expected = "Total: 1\nFiles with TODOs: 1\nFiles scanned: 1\nTODO: 1\n";
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+        assert_eq!(note.as_deref(), Some(expected));
+        assert!(outside.is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use super::options::PrintOptions;
+use super::utils::format_path;
+use std::io::{self, Write};
+use todo_tree_core::{Priority, ScanResult};
+
+/// Print one GitHub Actions workflow command per item so matches show up as
+/// inline annotations on pull requests.
+pub fn print_github<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    let mut all_items = result.all_items();
+    all_items.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.line.cmp(&b.1.line)));
+
+    for (path, item) in all_items {
+        let level = if item.priority == Priority::Critical {
+            "error"
+        } else {
+            "warning"
+        };
+
+        writeln!(
+            writer,
+            "::{} file={},line={},col={}::{}",
+            level,
+            escape_property(&format_path(&path, options)),
+            item.line,
+            item.column,
+            escape_data(&format!("{}: {}", item.tag, item.message))
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Escape the message part of a workflow command.
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a `key=value` property, where `:` and `,` are also delimiters.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::test_support::{item, render, result_in};
+
+    #[test]
+    fn critical_tags_are_errors_and_the_rest_warnings() {
+        let result = result_in(
+            "/repo",
+            vec![(
+                "src/main.rs",
+                vec![item("TODO", "tidy up", 3), item("BUG", "off by one", 9)],
+            )],
+        );
+
+        assert_eq!(
+            render(print_github, &result),
+            "::warning file=src/main.rs,line=3,col=4::TODO: tidy up\n\
+             ::error file=src/main.rs,line=9,col=4::BUG: off by one\n"
+        );
+    }
+
+    #[test]
+    fn escapes_workflow_command_delimiters() {
+        let result = result_in(
+            "/repo",
+            vec![("a,b.rs", vec![item("NOTE", "100% done\nnext", 1)])],
+        );
+
+        assert_eq!(
+            render(print_github, &result),
+            "::warning file=a%2Cb.rs,line=1,col=4::NOTE: 100%25 done%0Anext\n"
+        );
+    }
+}
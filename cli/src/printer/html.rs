@@ -0,0 +1,136 @@
+use super::options::PrintOptions;
+use super::utils::format_path;
+use std::io::{self, Write};
+use todo_tree_core::ScanResult;
+
+const STYLE: &str = "\
+table.todo-tree { border-collapse: collapse; font-family: sans-serif; }
+table.todo-tree th, table.todo-tree td { border: 1px solid #ddd; padding: 4px 8px; text-align: left; }
+table.todo-tree .priority-critical { color: #c0392b; }
+table.todo-tree .priority-high { color: #b7950b; }
+table.todo-tree .priority-medium { color: #1f8a9e; }
+table.todo-tree .priority-low { color: #27ae60; }";
+
+/// Standalone HTML page wrapping the fragment.
+pub fn print_html<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    writeln!(writer, "<!DOCTYPE html>")?;
+    writeln!(writer, "<html lang=\"en\">")?;
+    writeln!(writer, "<head>")?;
+    writeln!(writer, "<meta charset=\"utf-8\">")?;
+    writeln!(writer, "<title>TODO report</title>")?;
+    writeln!(writer, "<style>\n{}\n</style>", STYLE)?;
+    writeln!(writer, "</head>")?;
+    writeln!(writer, "<body>")?;
+    print_html_fragment(writer, result, options)?;
+    writeln!(writer, "</body>")?;
+    writeln!(writer, "</html>")?;
+    Ok(())
+}
+
+/// Only the results table, for embedding in an existing page.
+pub fn print_html_fragment<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    writeln!(writer, "<table class=\"todo-tree\">")?;
+    writeln!(
+        writer,
+        "<thead><tr><th>File</th><th>Line</th><th>Tag</th><th>Author</th><th>Message</th></tr></thead>"
+    )?;
+    writeln!(writer, "<tbody>")?;
+
+    let mut all_items = result.all_items();
+    all_items.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.line.cmp(&b.1.line)));
+
+    for (path, item) in all_items {
+        writeln!(
+            writer,
+            "<tr class=\"priority-{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            item.priority.display_name().to_lowercase(),
+            escape_html(&format_path(&path, options)),
+            item.line,
+            escape_html(&item.tag),
+            escape_html(&item.authors.join(", ")),
+            escape_html(&item.message)
+        )?;
+    }
+
+    writeln!(writer, "</tbody>")?;
+    writeln!(writer, "</table>")?;
+    Ok(())
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use todo_tree_core::{Priority, TodoItem};
+
+    fn result() -> ScanResult {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(
+            PathBuf::from("/repo/src/app.js"),
+            vec![TodoItem {
+                tag: "FIXME".to_string(),
+                message: "escape <b>tags</b> & quotes".to_string(),
+                line: 7,
+                column: 4,
+                priority: Priority::Critical,
+                ..Default::default()
+            }],
+        );
+        result
+    }
+
+    fn render(print: fn(&mut Vec<u8>, &ScanResult, &PrintOptions) -> io::Result<()>) -> String {
+        let options = PrintOptions {
+            base_path: Some(PathBuf::from("/repo")),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        print(&mut out, &result(), &options).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn fragment_contains_item_markup_without_document_wrapper() {
+        let output = render(print_html_fragment);
+
+        assert!(!output.contains("<html"));
+        assert!(!output.contains("<head>"));
+        assert!(output.starts_with("<table class=\"todo-tree\">"));
+        assert!(output.contains(
+            "<tr class=\"priority-critical\"><td>src/app.js</td><td>7</td><td>FIXME</td><td></td>\
+             <td>escape &lt;b&gt;tags&lt;/b&gt; &amp; quotes</td></tr>"
+        ));
+    }
+
+    #[test]
+    fn full_page_wraps_the_fragment() {
+        let page = render(print_html);
+        let fragment = render(print_html_fragment);
+
+        assert!(page.contains("<html lang=\"en\">"));
+        assert!(page.contains(&fragment));
+    }
+}
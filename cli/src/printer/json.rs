@@ -1,33 +1,79 @@
 use super::options::PrintOptions;
+use super::utils::{format_path, tag_description};
+use chrono::{DateTime, NaiveDate, Utc};
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::io::{self, Write};
-use todo_tree_core::ScanResult;
+use std::path::Path;
+use todo_tree_core::{Priority, ScanResult, TodoItem};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct JsonOutput {
     pub files: Vec<JsonFileEntry>,
     pub summary: JsonSummary,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct JsonFileEntry {
     pub path: String,
     pub items: Vec<JsonTodoItem>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct JsonTodoItem {
+    /// Stable id, as listed in `.todo-resolved`.
+    pub id: String,
     pub tag: String,
     pub message: String,
     pub line: usize,
     pub column: usize,
+    /// Column before tab expansion, with `--tab-width`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_column: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub author: Option<String>,
-    pub priority: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    pub priority: Priority,
+    /// [`Priority::severity`], for sorting on a number.
+    pub severity: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referenced_lines: Option<(usize, usize)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blame_author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blame_date: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub body: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_before: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_after: Vec<String>,
+    /// Other locations collapsed into this item by `--dedup`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<JsonLocation>,
+    /// The tag's description, with `--with-description`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct JsonLocation {
+    pub path: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct JsonSummary {
     pub total_count: usize,
     pub files_with_todos: usize,
@@ -52,28 +98,13 @@ impl JsonOutput {
             .sorted_files()
             .iter()
             .map(|(path, items)| {
-                let display_path = if options.full_paths {
-                    path.display().to_string()
-                } else if let Some(base) = &options.base_path {
-                    path.strip_prefix(base)
-                        .map(|p| p.display().to_string())
-                        .unwrap_or_else(|_| path.display().to_string())
-                } else {
-                    path.display().to_string()
-                };
+                let display_path = format_path(path, options);
 
                 JsonFileEntry {
                     path: display_path,
                     items: items
                         .iter()
-                        .map(|item| JsonTodoItem {
-                            tag: item.tag.clone(),
-                            message: item.message.clone(),
-                            line: item.line,
-                            column: item.column,
-                            author: item.author.clone(),
-                            priority: format!("{:?}", item.priority),
-                        })
+                        .map(|item| JsonTodoItem::new(result, path, item, options))
                         .collect(),
                 }
             })
@@ -81,13 +112,110 @@ impl JsonOutput {
 
         files.sort_by(|a, b| a.path.cmp(&b.path));
 
-        let summary = JsonSummary {
+        Self {
+            files,
+            summary: JsonSummary::from_scan_result(result),
+        }
+    }
+}
+
+impl JsonTodoItem {
+    pub fn new(result: &ScanResult, path: &Path, item: &TodoItem, options: &PrintOptions) -> Self {
+        Self {
+            id: result.item_id(path, item),
+            tag: item.tag.clone(),
+            message: item.message.clone(),
+            line: item.line,
+            column: item.column,
+            raw_column: item.raw_column,
+            author: item.author.clone(),
+            authors: item.authors.clone(),
+            priority: item.priority,
+            severity: item.priority.severity(),
+            referenced_lines: item.referenced_lines,
+            issue: item.issue.clone(),
+            due: item.due,
+            blame_author: item.blame_author.clone(),
+            blame_date: item.blame_date,
+            body: item.body.clone(),
+            context_before: item.context_before.clone(),
+            context_after: item.context_after.clone(),
+            duplicates: item
+                .duplicates
+                .iter()
+                .map(|location| JsonLocation {
+                    path: format_path(&location.path, options),
+                    line: location.line,
+                })
+                .collect(),
+            description: tag_description(&item.tag, options).map(str::to_string),
+        }
+    }
+}
+
+impl JsonSummary {
+    pub fn from_scan_result(result: &ScanResult) -> Self {
+        Self {
             total_count: result.summary.total_count,
             files_with_todos: result.summary.files_with_todos,
             files_scanned: result.summary.files_scanned,
             tag_counts: result.summary.tag_counts.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use todo_tree_core::{TagRegistry, TodoItem};
+
+    #[test]
+    fn includes_tag_description_when_requested() {
+        let item = |tag: &str| TodoItem {
+            tag: tag.to_string(),
+            message: "msg".to_string(),
+            line: 1,
+            ..Default::default()
         };
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(PathBuf::from("/repo/a.rs"), vec![item("TODO")]);
+        result.add_file(PathBuf::from("/repo/b.rs"), vec![item("CUSTOM")]);
+
+        let mut options = PrintOptions {
+            base_path: Some(PathBuf::from("/repo")),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(JsonOutput::from_scan_result(&result, &options)).unwrap();
+        assert!(json["files"][0]["items"][0].get("description").is_none());
+
+        options.descriptions = Some(TagRegistry::default());
+        let json = serde_json::to_value(JsonOutput::from_scan_result(&result, &options)).unwrap();
+        assert_eq!(
+            json["files"][0]["items"][0]["description"],
+            "General TODO items"
+        );
+        assert!(json["files"][1]["items"][0].get("description").is_none());
+    }
+
+    #[test]
+    fn priority_is_a_lowercase_string_with_a_numeric_severity() {
+        let item = TodoItem {
+            tag: "BUG".to_string(),
+            message: "crash".to_string(),
+            line: 1,
+            priority: Priority::Critical,
+            ..Default::default()
+        };
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(PathBuf::from("/repo/a.rs"), vec![item]);
+
+        let mut out = Vec::new();
+        let json = JsonOutput::from_scan_result(&result, &PrintOptions::default());
+        serde_json::to_writer(&mut out, &json).unwrap();
+        let output = String::from_utf8(out).unwrap();
 
-        Self { files, summary }
+        assert!(output.contains(r#""priority":"critical""#));
+        assert!(output.contains(r#""severity":3"#));
     }
 }
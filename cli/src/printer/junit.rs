@@ -0,0 +1,157 @@
+use super::options::PrintOptions;
+use super::utils::{format_path, to_posix_separators};
+use std::io::{self, Write};
+use todo_tree_core::ScanResult;
+
+/// JUnit XML report for CI test viewers: one `<testcase>` per file, failing
+/// with one `<failure>` per item in it.
+pub fn print_junit<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    let files = result.sorted_files();
+    let failures = result.summary.total_count;
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<testsuites name="todo-tree" tests="{}" failures="{}">"#,
+        files.len(),
+        failures
+    )?;
+    writeln!(
+        writer,
+        r#"  <testsuite name="todo-tree" tests="{}" failures="{}">"#,
+        files.len(),
+        failures
+    )?;
+
+    for (path, items) in files {
+        let path = escape_xml(&to_posix_separators(&format_path(path, options)));
+        writeln!(
+            writer,
+            r#"    <testcase classname="todo-tree" name="{}" file="{}">"#,
+            path, path
+        )?;
+        for item in items {
+            writeln!(
+                writer,
+                r#"      <failure type="{}" message="{}">{}:{}:{}</failure>"#,
+                escape_xml(&item.tag),
+                escape_xml(&item.message),
+                path,
+                item.line,
+                item.column
+            )?;
+        }
+        writeln!(writer, "    </testcase>")?;
+    }
+
+    writeln!(writer, "  </testsuite>")?;
+    writeln!(writer, "</testsuites>")?;
+    Ok(())
+}
+
+/// Escape markup characters for use in text and attributes. Control
+/// characters XML 1.0 cannot represent at all become U+FFFD.
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push_str(&format!("&#{};", c as u32)),
+            c if c.is_control() => escaped.push('\u{FFFD}'),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::test_support::{item, render, result_in};
+
+    /// Check that every element is closed in order and that no text or
+    /// attribute value carries a raw `<`, `>` or stray `&`.
+    fn assert_well_formed(xml: &str) {
+        let body = xml
+            .strip_prefix(r#"<?xml version="1.0" encoding="UTF-8"?>"#)
+            .expect("missing XML declaration");
+        let mut open: Vec<&str> = Vec::new();
+        let mut rest = body;
+        while let Some(start) = rest.find('<') {
+            assert_text(&rest[..start]);
+            let end = start + rest[start..].find('>').expect("unterminated tag");
+            let tag = &rest[start + 1..end];
+            assert!(!tag.contains('<'), "raw < in {:?}", tag);
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open.pop(), Some(name));
+            } else if !tag.ends_with('/') {
+                let name = tag.split_whitespace().next().unwrap();
+                assert_text(tag);
+                open.push(name);
+            }
+            rest = &rest[end + 1..];
+        }
+        assert_text(rest);
+        assert!(open.is_empty(), "unclosed elements: {:?}", open);
+    }
+
+    fn assert_text(text: &str) {
+        for (idx, _) in text.match_indices('&') {
+            let entity = &text[idx..text[idx..]
+                .find(';')
+                .map_or(text.len(), |end| idx + end + 1)];
+            assert!(
+                ["&amp;", "&lt;", "&gt;", "&quot;", "&apos;"].contains(&entity)
+                    || entity.starts_with("&#"),
+                "stray & in {:?}",
+                text
+            );
+        }
+        assert!(!text.contains('>'), "raw > in {:?}", text);
+    }
+
+    #[test]
+    fn one_failure_per_item_in_well_formed_xml() {
+        let result = result_in(
+            "/repo",
+            vec![
+                (
+                    "src/lib.rs",
+                    vec![
+                        item("TODO", "handle <T> & \"quoted\" 'args'", 3),
+                        item("FIXME", "bell\u{7} here", 9),
+                    ],
+                ),
+                ("README.md", vec![item("NOTE", "docs", 1)]),
+            ],
+        );
+
+        let xml = render(print_junit, &result);
+
+        assert_well_formed(&xml);
+        assert!(xml.contains(r#"<testsuites name="todo-tree" tests="2" failures="3">"#));
+        assert_eq!(xml.matches("<testcase ").count(), 2);
+        assert_eq!(xml.matches("<failure ").count(), 3);
+        assert!(xml.contains(
+            r#"<failure type="TODO" message="handle &lt;T&gt; &amp; &quot;quoted&quot; &apos;args&apos;">src/lib.rs:3:4</failure>"#
+        ));
+        assert!(xml.contains("bell\u{FFFD} here"));
+    }
+
+    #[test]
+    fn empty_result_is_an_empty_passing_suite() {
+        let xml = render(print_junit, &result_in("/repo", vec![]));
+
+        assert_well_formed(&xml);
+        assert!(xml.contains(r#"tests="0" failures="0""#));
+        assert!(!xml.contains("<testcase"));
+    }
+}
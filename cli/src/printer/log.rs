@@ -0,0 +1,88 @@
+use super::options::PrintOptions;
+use super::utils::format_path;
+use chrono::{SecondsFormat, Utc};
+use std::io::{self, Write};
+use todo_tree_core::{Priority, ScanResult};
+
+/// Print one syslog-style line per item:
+/// `<timestamp> <LEVEL> <file>:<line> [<tag>] <message>`.
+pub fn print_log<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    write_log(writer, result, options, &timestamp)
+}
+
+fn write_log<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+    timestamp: &str,
+) -> io::Result<()> {
+    let mut all_items = result.all_items();
+    all_items.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.line.cmp(&b.1.line)));
+
+    for (path, item) in all_items {
+        writeln!(
+            writer,
+            "{} {} {}:{} [{}] {}",
+            timestamp,
+            log_level(item.priority),
+            format_path(&path, options),
+            item.line,
+            item.tag,
+            item.message.replace(['\n', '\r'], " ")
+        )?;
+    }
+
+    Ok(())
+}
+
+fn log_level(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Critical => "CRIT",
+        Priority::High => "WARN",
+        Priority::Medium => "INFO",
+        Priority::Low => "DEBUG",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use todo_tree_core::TodoItem;
+
+    #[test]
+    fn level_follows_item_priority() {
+        let items = ["BUG", "HACK", "TODO", "NOTE"]
+            .iter()
+            .enumerate()
+            .map(|(i, tag)| TodoItem {
+                tag: tag.to_string(),
+                message: "msg".to_string(),
+                line: i + 1,
+                priority: Priority::from_tag(tag),
+                ..Default::default()
+            })
+            .collect();
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(PathBuf::from("/repo/main.rs"), items);
+        let options = PrintOptions {
+            base_path: Some(PathBuf::from("/repo")),
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        write_log(&mut out, &result, &options, "2024-01-01T00:00:00Z").unwrap();
+        let output = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[0], "2024-01-01T00:00:00Z CRIT main.rs:1 [BUG] msg");
+        assert_eq!(lines[1], "2024-01-01T00:00:00Z WARN main.rs:2 [HACK] msg");
+        assert_eq!(lines[2], "2024-01-01T00:00:00Z INFO main.rs:3 [TODO] msg");
+        assert_eq!(lines[3], "2024-01-01T00:00:00Z DEBUG main.rs:4 [NOTE] msg");
+    }
+}
@@ -0,0 +1,71 @@
+use super::options::PrintOptions;
+use super::utils::format_path;
+use std::io::{self, Write};
+use todo_tree_core::ScanResult;
+
+/// `file:line: TAG message`, one line per item and nothing else: no colors,
+/// links or summary, and no output at all when nothing was found.
+pub fn print_minimal<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    for (path, item) in result.sorted_items(options.sort) {
+        let message = item.message.replace(['\n', '\r'], " ");
+        write!(
+            writer,
+            "{}:{}: {}",
+            format_path(&path, options),
+            item.line,
+            item.tag
+        )?;
+        if message.is_empty() {
+            writeln!(writer)?;
+        } else {
+            writeln!(writer, " {}", message)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use todo_tree_core::TodoItem;
+
+    #[test]
+    fn prints_exactly_file_line_tag_and_message() {
+        let item = |tag: &str, message: &str, line| TodoItem {
+            tag: tag.to_string(),
+            message: message.to_string(),
+            line,
+            column: 4,
+            author: Some("alice".to_string()),
+            ..Default::default()
+        };
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(
+            PathBuf::from("/repo/src/lib.rs"),
+            vec![item("TODO", "share me", 3), item("FIXME", "", 7)],
+        );
+        let options = PrintOptions {
+            base_path: Some(PathBuf::from("/repo")),
+            colored: true,
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        print_minimal(&mut out, &result, &options).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "src/lib.rs:3: TODO share me\nsrc/lib.rs:7: FIXME\n"
+        );
+
+        let mut out = Vec::new();
+        print_minimal(&mut out, &ScanResult::new(PathBuf::from("/repo")), &options).unwrap();
+        assert!(out.is_empty());
+    }
+}
@@ -1,17 +1,57 @@
+pub mod annotations;
+pub mod ascii_tree;
+pub mod clipboard;
+pub mod codequality;
+pub mod confluence;
+pub mod csv;
+pub mod debug;
 pub mod flat;
+pub mod git_notes;
+pub mod github;
+pub mod html;
 pub mod json;
+pub mod junit;
+pub mod log;
+pub mod minimal;
+pub mod ndjson;
 pub mod options;
+pub mod patch;
+pub mod sarif;
+pub mod schema;
 pub mod summary;
 pub mod tree;
+pub mod tree_json;
+pub mod tsv;
 pub mod utils;
 
+#[cfg(test)]
+mod test_support;
+
+use annotations::print_annotations;
+use ascii_tree::print_ascii_tree;
+use clipboard::{print_clipboard, system_clipboard};
+use codequality::print_codequality;
+use colored::Colorize;
+use confluence::print_confluence;
+use csv::print_csv;
 use flat::print_flat;
+use git_notes::print_git_notes;
+use github::print_github;
+use html::{print_html, print_html_fragment};
 use json::print_json;
+use junit::print_junit;
+use log::print_log;
+use minimal::print_minimal;
+use ndjson::print_ndjson;
 pub use options::{OutputFormat, PrintOptions};
+use patch::print_patch;
+use sarif::print_sarif;
 use std::io::{self, Write};
 use summary::print_summary;
 use todo_tree_core::ScanResult;
 use tree::print_tree;
+use tree_json::print_tree_json;
+use tsv::print_tsv;
 
 pub struct Printer {
     options: PrintOptions,
@@ -19,7 +59,7 @@ pub struct Printer {
 
 impl Printer {
     pub fn new(options: PrintOptions) -> Self {
-        if !options.colored {
+        if !options.colored || options.format == OutputFormat::Clipboard {
             colored::control::set_override(false);
         }
         Self { options }
@@ -32,17 +72,104 @@ impl Printer {
     }
 
     pub fn print_to<W: Write>(&self, writer: &mut W, result: &ScanResult) -> io::Result<()> {
+        let full = result;
+        let limited;
+        let result = match self.options.limit {
+            Some(limit)
+                if self.options.format.is_human_readable() && limit < full.summary.total_count =>
+            {
+                limited = full.limited(self.options.sort, limit);
+                &limited
+            }
+            _ => full,
+        };
+
         match self.options.format {
             OutputFormat::Tree => print_tree(writer, result, &self.options)?,
             OutputFormat::Flat => print_flat(writer, result, &self.options)?,
             OutputFormat::Json => print_json(writer, result, &self.options)?,
+            OutputFormat::TreeJson => print_tree_json(writer, result, &self.options)?,
+            OutputFormat::Ndjson => print_ndjson(writer, result, &self.options)?,
+            OutputFormat::Tsv => print_tsv(writer, result, &self.options)?,
+            OutputFormat::Csv => print_csv(writer, result, &self.options)?,
+            OutputFormat::Confluence => print_confluence(writer, result, &self.options)?,
+            OutputFormat::Html => print_html(writer, result, &self.options)?,
+            OutputFormat::HtmlFragment => print_html_fragment(writer, result, &self.options)?,
+            OutputFormat::Github => print_github(writer, result, &self.options)?,
+            OutputFormat::Sarif => print_sarif(writer, result, &self.options)?,
+            OutputFormat::Codequality => print_codequality(writer, result, &self.options)?,
+            OutputFormat::Junit => print_junit(writer, result, &self.options)?,
+            OutputFormat::Log => print_log(writer, result, &self.options)?,
+            OutputFormat::AsciiTree => print_ascii_tree(writer, result, &self.options)?,
+            OutputFormat::Clipboard => {
+                print_clipboard(writer, result, &self.options, system_clipboard())?
+            }
+            OutputFormat::Patch => print_patch(writer, result, &self.options)?,
+            OutputFormat::Minimal => print_minimal(writer, result, &self.options)?,
+            OutputFormat::GitNotes => print_git_notes(writer, result, &self.options)?,
+            OutputFormat::EditorconfigAnnotations => {
+                print_annotations(writer, result, &self.options)?
+            }
         }
 
-        if self.options.show_summary && self.options.format != OutputFormat::Json {
+        let hidden = full.summary.total_count - result.summary.total_count;
+        if hidden > 0 {
+            let note = format!("… and {} more", hidden);
+            if self.options.colored {
+                writeln!(writer, "{}", note.dimmed())?;
+            } else {
+                writeln!(writer, "{}", note)?;
+            }
+        }
+
+        if self.options.show_summary && self.options.format.is_human_readable() {
             writeln!(writer)?;
-            print_summary(writer, result, &self.options)?;
+            print_summary(writer, full, &self.options)?;
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use todo_tree_core::TodoItem;
+
+    #[test]
+    fn limit_shows_the_first_items_and_counts_the_rest() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        let items = (1..=5)
+            .map(|line| TodoItem {
+                tag: "TODO".to_string(),
+                message: format!("item {}", line),
+                line,
+                column: 4,
+                ..Default::default()
+            })
+            .collect();
+        result.add_file(PathBuf::from("/repo/a.rs"), items);
+        let printer = Printer::new(PrintOptions {
+            format: OutputFormat::Flat,
+            colored: false,
+            clickable_links: false,
+            base_path: Some(PathBuf::from("/repo")),
+            limit: Some(2),
+            ..Default::default()
+        });
+
+        let mut out = Vec::new();
+        printer.print_to(&mut out, &result).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "a.rs TODO[Medium] 1:4: item 1\n\
+             a.rs TODO[Medium] 2:4: item 2\n\
+             … and 3 more\n\
+             \n\
+             Found 5 TODO items in 1 files (1 files scanned)\n  \
+             TODO: 5\n"
+        );
+    }
+}
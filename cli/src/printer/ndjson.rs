@@ -0,0 +1,76 @@
+use super::options::PrintOptions;
+use super::utils::format_path;
+use serde::Serialize;
+use std::io::{self, Write};
+use todo_tree_core::{ScanResult, TodoItem};
+
+/// One line of `--ndjson` output: the item's own fields plus where it is.
+#[derive(Debug, Serialize)]
+struct NdjsonItem<'a> {
+    path: String,
+    #[serde(flatten)]
+    item: &'a TodoItem,
+    severity: u8,
+}
+
+/// One compact JSON object per item per line, so consumers such as `jq`
+/// can process results as they arrive.
+pub fn print_ndjson<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    for (path, item) in result.sorted_items(options.sort) {
+        let line = NdjsonItem {
+            path: format_path(&path, options),
+            item: &item,
+            severity: item.priority.severity(),
+        };
+        serde_json::to_writer(&mut *writer, &line).map_err(io::Error::other)?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn every_line_is_a_json_object_with_its_path() {
+        let item = |line: usize, message: &str| TodoItem {
+            tag: "TODO".to_string(),
+            message: message.to_string(),
+            line,
+            column: 4,
+            ..Default::default()
+        };
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(
+            PathBuf::from("/repo/src/lib.rs"),
+            vec![item(1, "first"), item(9, "has \"quotes\"\nand a newline")],
+        );
+        result.add_file(PathBuf::from("/repo/main.rs"), vec![item(2, "third")]);
+        let options = PrintOptions {
+            base_path: Some(PathBuf::from("/repo")),
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        print_ndjson(&mut out, &result, &options).unwrap();
+        let output = String::from_utf8(out).unwrap();
+
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["path"], "main.rs");
+        assert_eq!(lines[1]["path"], "src/lib.rs");
+        assert_eq!(lines[1]["line"], 1);
+        assert_eq!(lines[2]["message"], "has \"quotes\"\nand a newline");
+        assert_eq!(lines[2]["tag"], "TODO");
+    }
+}
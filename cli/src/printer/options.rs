@@ -1,10 +1,107 @@
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use todo_tree_core::{SortKey, TagRegistry};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum OutputFormat {
+    #[value(name = "tree", help = "Tree view grouped by file")]
     Tree,
+    #[value(name = "flat", help = "One line per item")]
     Flat,
+    #[value(name = "json", help = "JSON document")]
     Json,
+    #[value(
+        name = "tree-json",
+        help = "JSON document nesting directories like the tree view"
+    )]
+    TreeJson,
+    #[value(name = "ndjson", help = "One JSON object per item per line")]
+    Ndjson,
+    #[value(name = "tsv", help = "Tab-separated values")]
+    Tsv,
+    #[value(name = "csv", help = "Comma-separated values for spreadsheets")]
+    Csv,
+    #[value(name = "confluence", help = "Confluence wiki markup table")]
+    Confluence,
+    #[value(name = "html", help = "Standalone HTML page")]
+    Html,
+    #[value(
+        name = "html-fragment",
+        help = "HTML table for embedding in an existing page"
+    )]
+    HtmlFragment,
+    #[value(name = "github", help = "GitHub Actions workflow annotations")]
+    Github,
+    #[value(name = "sarif", help = "SARIF 2.1.0 log for code-scanning tools")]
+    Sarif,
+    #[value(name = "log", help = "Syslog-style lines with a severity level")]
+    Log,
+    #[value(name = "ascii-tree", help = "Plain-ASCII directory tree")]
+    AsciiTree,
+    #[value(
+        name = "clipboard",
+        help = "Copy the flat list to the system clipboard instead of printing it"
+    )]
+    Clipboard,
+    #[value(
+        name = "patch",
+        help = "Unified diff deleting resolved-looking items, for `git apply`"
+    )]
+    Patch,
+    #[value(
+        name = "editorconfig-annotations",
+        help = "Per-file annotation listing for editor plugins to load without rescanning"
+    )]
+    EditorconfigAnnotations,
+    #[value(
+        name = "minimal",
+        help = "Stable `file:line: TAG message` lines for scripts, never colored"
+    )]
+    Minimal,
+    #[value(
+        name = "git-notes",
+        help = "Attach the summary to HEAD as a git note (needs --allow-git-write)"
+    )]
+    GitNotes,
+    #[value(
+        name = "codequality",
+        help = "GitLab Code Quality report for merge request widgets"
+    )]
+    Codequality,
+    #[value(
+        name = "junit",
+        help = "JUnit XML with a failing test case per file, for CI test reports"
+    )]
+    Junit,
+}
+
+impl OutputFormat {
+    /// Format implied by an output file's extension, e.g. `report.json`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        let format = match extension.as_str() {
+            "json" => OutputFormat::Json,
+            "ndjson" | "jsonl" => OutputFormat::Ndjson,
+            "csv" => OutputFormat::Csv,
+            "tsv" => OutputFormat::Tsv,
+            "html" | "htm" => OutputFormat::Html,
+            "sarif" => OutputFormat::Sarif,
+            "xml" => OutputFormat::Junit,
+            "log" => OutputFormat::Log,
+            "patch" | "diff" => OutputFormat::Patch,
+            _ => return None,
+        };
+        Some(format)
+    }
+
+    /// Whether the format is meant for humans and should be followed by the summary.
+    pub fn is_human_readable(&self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Tree | OutputFormat::Flat | OutputFormat::AsciiTree
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -17,6 +114,24 @@ pub struct PrintOptions {
     pub base_path: Option<PathBuf>,
     pub show_summary: bool,
     pub group_by_tag: bool,
+    /// Show only the first N items of each file, by line. With a non-path
+    /// `sort`, the items kept from every file are then sorted together.
+    pub head: Option<usize>,
+    /// Show only the last N items of each file, as for `head`.
+    pub tail: Option<usize>,
+    /// Show only the first N items overall, in `sort` order, in the
+    /// human-readable formats. The summary still counts everything.
+    pub limit: Option<usize>,
+    /// Order of the flat list; anything but `Path` mixes items across files.
+    pub sort: SortKey,
+    /// Annotate each node of the ASCII tree with its subtree's item count.
+    pub show_counts: bool,
+    /// Render path separators as `/` on every platform.
+    pub posix_paths: bool,
+    /// Tags to look descriptions up in; `None` leaves descriptions out.
+    pub descriptions: Option<TagRegistry>,
+    /// Add a line to the summary counting tags written in a non-canonical case.
+    pub case_report: bool,
 }
 
 impl Default for PrintOptions {
@@ -30,6 +145,14 @@ impl Default for PrintOptions {
             base_path: None,
             show_summary: true,
             group_by_tag: false,
+            head: None,
+            tail: None,
+            limit: None,
+            sort: SortKey::Path,
+            show_counts: false,
+            posix_paths: false,
+            descriptions: None,
+            case_report: false,
         }
     }
 }
@@ -0,0 +1,219 @@
+use super::options::PrintOptions;
+use super::utils::{format_path, to_posix_separators};
+use crate::language;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::Path;
+use todo_tree_core::{ScanResult, TodoItem};
+
+/// Unchanged lines shown around each change, as with `diff -u`.
+const CONTEXT: usize = 3;
+
+/// A unified diff deleting every item's comment, to review and pipe to
+/// `git apply`. A comment trailing code is cut off and the code kept. Files
+/// that can no longer be read are left out.
+pub fn print_patch<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    for (path, items) in result.sorted_files() {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let lines: Vec<&str> = content
+            .split_inclusive('\n')
+            .map(|line| line.strip_suffix('\n').unwrap_or(line))
+            .collect();
+        let changes = removals(path, &lines, items);
+        if changes.is_empty() {
+            continue;
+        }
+
+        let name = to_posix_separators(&format_path(path, options));
+        writeln!(writer, "diff --git a/{0} b/{0}", name)?;
+        writeln!(writer, "--- a/{}", name)?;
+        writeln!(writer, "+++ b/{}", name)?;
+        let missing_final_newline = !content.ends_with('\n');
+        write_hunks(writer, &lines, missing_final_newline, &changes)?;
+    }
+
+    Ok(())
+}
+
+/// Changed lines by index: `None` deletes the line, `Some` replaces it.
+fn removals(path: &Path, lines: &[&str], items: &[TodoItem]) -> BTreeMap<usize, Option<String>> {
+    let syntax = language::syntax_for_path(path);
+    let mut changes = BTreeMap::new();
+
+    for item in items {
+        let idx = item.line - 1;
+        let Some(line) = lines.get(idx) else {
+            continue;
+        };
+        let (text, eol) = match line.strip_suffix('\r') {
+            Some(text) => (text, "\r"),
+            None => (*line, ""),
+        };
+        let comment = syntax
+            .as_ref()
+            .and_then(|syntax| syntax.comment_start(text))
+            .unwrap_or(0);
+        let code = text[..comment].trim_end();
+
+        if code.is_empty() {
+            for body_idx in idx..=idx + item.body.len() {
+                changes.insert(body_idx, None);
+            }
+        } else {
+            changes.insert(idx, Some(format!("{}{}", code, eol)));
+        }
+    }
+
+    changes.retain(|idx, _| *idx < lines.len());
+    changes
+}
+
+fn write_hunks<W: Write>(
+    writer: &mut W,
+    lines: &[&str],
+    missing_final_newline: bool,
+    changes: &BTreeMap<usize, Option<String>>,
+) -> io::Result<()> {
+    // Changes close enough for their context to touch share a hunk.
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for &idx in changes.keys() {
+        match groups.last_mut() {
+            Some(group) if idx - group[group.len() - 1] <= 2 * CONTEXT + 1 => group.push(idx),
+            _ => groups.push(vec![idx]),
+        }
+    }
+
+    let last_line = lines.len() - 1;
+    let no_newline = |writer: &mut W, idx: usize| -> io::Result<()> {
+        if missing_final_newline && idx == last_line {
+            writeln!(writer, "\\ No newline at end of file")?;
+        }
+        Ok(())
+    };
+
+    let mut removed_so_far = 0;
+    for group in groups {
+        let first = group[0].saturating_sub(CONTEXT);
+        let last = (group[group.len() - 1] + CONTEXT).min(last_line);
+        let old_len = last - first + 1;
+        let removed = group.iter().filter(|idx| changes[idx].is_none()).count();
+        let new_len = old_len - removed;
+        let new_start = first - removed_so_far + usize::from(new_len > 0);
+        writeln!(
+            writer,
+            "@@ -{},{} +{},{} @@",
+            first + 1,
+            old_len,
+            new_start,
+            new_len
+        )?;
+
+        for (idx, line) in lines.iter().enumerate().take(last + 1).skip(first) {
+            match changes.get(&idx) {
+                None => writeln!(writer, " {}", line)?,
+                Some(None) => writeln!(writer, "-{}", line)?,
+                Some(Some(code)) => {
+                    writeln!(writer, "-{}", line)?;
+                    no_newline(writer, idx)?;
+                    writeln!(writer, "+{}", code)?;
+                }
+            }
+            no_newline(writer, idx)?;
+        }
+
+        removed_so_far += removed;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TodoParser;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn patch_for(name: &str, content: &str) -> String {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("todo_patch_{name}_{unique}"));
+        std::fs::create_dir_all(&root).unwrap();
+        let file = root.join("lib.rs");
+        std::fs::write(&file, content).unwrap();
+
+        let parser = TodoParser::new(&["TODO".to_string()], true);
+        let mut result = ScanResult::new(root.clone());
+        result.add_file(file, parser.parse_content(content));
+        let options = PrintOptions {
+            base_path: Some(root.clone()),
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        print_patch(&mut out, &result, &options).unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn removes_the_comment_line_with_context() {
+        let content =
+            "fn a() {}\nfn b() {}\n\n// TODO: done\nfn c() {}\nfn d() {}\nfn e() {}\nfn f() {}\n";
+
+        assert_eq!(
+            patch_for("line", content),
+            "diff --git a/lib.rs b/lib.rs\n\
+             --- a/lib.rs\n\
+             +++ b/lib.rs\n\
+             @@ -1,7 +1,6 @@\n \
+             fn a() {}\n \
+             fn b() {}\n \
+             \n\
+             -// TODO: done\n \
+             fn c() {}\n \
+             fn d() {}\n \
+             fn e() {}\n"
+        );
+    }
+
+    #[test]
+    fn keeps_code_before_a_trailing_comment() {
+        let content = "let x = 1; // TODO: done";
+
+        assert_eq!(
+            patch_for("trailing", content),
+            "diff --git a/lib.rs b/lib.rs\n\
+             --- a/lib.rs\n\
+             +++ b/lib.rs\n\
+             @@ -1,1 +1,1 @@\n\
+             -let x = 1; // TODO: done\n\
+             \\ No newline at end of file\n\
+             +let x = 1;\n\
+             \\ No newline at end of file\n"
+        );
+    }
+
+    #[test]
+    fn unreadable_files_produce_no_patch() {
+        let mut result = ScanResult::new(PathBuf::from("/nonexistent"));
+        let item = TodoItem {
+            tag: "TODO".to_string(),
+            line: 1,
+            ..Default::default()
+        };
+        result.add_file(PathBuf::from("/nonexistent/lib.rs"), vec![item]);
+
+        let mut out = Vec::new();
+        print_patch(&mut out, &result, &PrintOptions::default()).unwrap();
+        assert!(out.is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+use super::options::PrintOptions;
+use super::utils::{format_path, to_posix_separators};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::io::{self, Write};
+use todo_tree_core::{Priority, ScanResult};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+#[derive(Debug, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub information_uri: &'static str,
+    pub rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifRule {
+    pub id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub level: &'static str,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    pub region: SarifRegion,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    pub start_line: usize,
+    pub start_column: usize,
+}
+
+pub fn print_sarif<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    let log = SarifLog::from_scan_result(result, options);
+    let json_str = serde_json::to_string_pretty(&log).map_err(io::Error::other)?;
+    writeln!(writer, "{}", json_str)?;
+    Ok(())
+}
+
+impl SarifLog {
+    pub fn from_scan_result(result: &ScanResult, options: &PrintOptions) -> Self {
+        let mut all_items = result.all_items();
+        all_items.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.line.cmp(&b.1.line)));
+
+        let rules: BTreeSet<&str> = all_items
+            .iter()
+            .map(|(_, item)| item.tag.as_str())
+            .collect();
+        let rules = rules
+            .into_iter()
+            .map(|tag| SarifRule {
+                id: tag.to_string(),
+            })
+            .collect();
+
+        let results = all_items
+            .iter()
+            .map(|(path, item)| SarifResult {
+                rule_id: item.tag.clone(),
+                level: sarif_level(item.priority),
+                message: SarifMessage {
+                    text: format!("{}: {}", item.tag, item.message),
+                },
+                locations: vec![SarifLocation {
+                    physical_location: SarifPhysicalLocation {
+                        artifact_location: SarifArtifactLocation {
+                            uri: to_posix_separators(&format_path(path, options)),
+                        },
+                        region: SarifRegion {
+                            start_line: item.line,
+                            start_column: item.column,
+                        },
+                    },
+                }],
+            })
+            .collect();
+
+        Self {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "todo-tree",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: env!("CARGO_PKG_REPOSITORY"),
+                        rules,
+                    },
+                },
+                results,
+            }],
+        }
+    }
+}
+
+fn sarif_level(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Critical => "error",
+        Priority::High => "warning",
+        Priority::Medium | Priority::Low => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::test_support::{item_at, render, result_in};
+
+    #[test]
+    fn emits_one_result_per_item_with_rule_and_region() {
+        let result = result_in(
+            "/repo",
+            vec![(
+                "src/lib.rs",
+                vec![
+                    item_at("TODO", "tidy up", 3, 5),
+                    item_at("BUG", "off by one", 9, 1),
+                ],
+            )],
+        );
+
+        let sarif: serde_json::Value = serde_json::from_str(&render(print_sarif, &result)).unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "todo-tree");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "BUG");
+        assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "TODO");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["ruleId"], "TODO");
+        assert_eq!(results[0]["level"], "note");
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/lib.rs");
+        assert_eq!(location["region"]["startLine"], 3);
+        assert_eq!(location["region"]["startColumn"], 5);
+        assert_eq!(results[1]["ruleId"], "BUG");
+        assert_eq!(results[1]["level"], "error");
+    }
+}
@@ -0,0 +1,85 @@
+use super::json::JsonOutput;
+use serde_json::Value;
+
+/// JSON Schema (draft 2020-12) of the `--json` document, derived from
+/// [`JsonOutput`] so it cannot drift from what is printed.
+pub fn output_schema() -> Value {
+    schemars::schema_for!(JsonOutput).to_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::json::print_json;
+    use super::super::options::PrintOptions;
+    use super::*;
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use std::path::PathBuf;
+    use todo_tree_core::{ItemLocation, ScanResult, TagRegistry, TodoItem};
+
+    #[test]
+    fn schema_is_draft_2020_12_with_the_top_level_contract() {
+        let schema = output_schema();
+
+        assert_eq!(
+            schema["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+        assert!(schema["properties"]["files"].is_object());
+        assert!(schema["properties"]["summary"].is_object());
+    }
+
+    #[test]
+    fn json_output_validates_against_the_schema() {
+        let item = TodoItem {
+            tag: "TODO".to_string(),
+            message: "msg".to_string(),
+            line: 2,
+            column: 3,
+            raw_column: Some(2),
+            author: Some("alice".to_string()),
+            authors: vec!["alice".to_string()],
+            referenced_lines: Some((1, 4)),
+            issue: Some("#1".to_string()),
+            due: NaiveDate::from_ymd_opt(2030, 1, 1),
+            blame_author: Some("bob".to_string()),
+            blame_date: Some(Utc.timestamp_opt(0, 0).unwrap()),
+            body: vec!["more".to_string()],
+            context_before: vec!["before".to_string()],
+            context_after: vec!["after".to_string()],
+            duplicates: vec![ItemLocation {
+                path: PathBuf::from("/repo/b.rs"),
+                line: 9,
+            }],
+            ..Default::default()
+        };
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(PathBuf::from("/repo/a.rs"), vec![item]);
+        result.add_file(
+            PathBuf::from("/repo/c.rs"),
+            vec![TodoItem {
+                tag: "FIXME".to_string(),
+                line: 1,
+                column: 1,
+                ..Default::default()
+            }],
+        );
+        let options = PrintOptions {
+            descriptions: Some(TagRegistry::default()),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        print_json(&mut out, &result, &options).unwrap();
+        let output: Value = serde_json::from_slice(&out).unwrap();
+
+        let validator = jsonschema::draft202012::new(&output_schema()).unwrap();
+        let errors: Vec<String> = validator
+            .iter_errors(&output)
+            .map(|err| err.to_string())
+            .collect();
+        assert!(errors.is_empty(), "{:#?}", errors);
+
+        let mut extra = output.clone();
+        extra["files"][0]["items"][0]["unknown"] = Value::Bool(true);
+        assert!(!validator.is_valid(&extra));
+    }
+}
@@ -1,8 +1,9 @@
 use super::options::PrintOptions;
 use super::utils::colorize_tag;
+use crate::utils::display::group_thousands;
 use colored::Colorize;
 use std::io::{self, Write};
-use todo_tree_core::ScanResult;
+use todo_tree_core::{ScanResult, ScanSummary};
 
 pub fn print_summary<W: Write>(
     writer: &mut W,
@@ -11,7 +12,9 @@ pub fn print_summary<W: Write>(
 ) -> io::Result<()> {
     let summary_line = format!(
         "Found {} TODO items in {} files ({} files scanned)",
-        result.summary.total_count, result.summary.files_with_todos, result.summary.files_scanned
+        group_thousands(result.summary.total_count),
+        group_thousands(result.summary.files_with_todos),
+        group_thousands(result.summary.files_scanned)
     );
 
     if options.colored {
@@ -21,12 +24,12 @@ pub fn print_summary<W: Write>(
     }
 
     if !result.summary.tag_counts.is_empty() {
-        let mut tags: Vec<_> = result.summary.tag_counts.iter().collect();
-        tags.sort_by(|a, b| b.1.cmp(a.1));
-
-        let breakdown: Vec<String> = tags
-            .iter()
+        let breakdown: Vec<String> = result
+            .summary
+            .tags_by_count()
+            .into_iter()
             .map(|(tag, count)| {
+                let count = group_thousands(count);
                 if options.colored {
                     format!("{}: {}", colorize_tag(tag, options), count)
                 } else {
@@ -38,5 +41,118 @@ pub fn print_summary<W: Write>(
         writeln!(writer, "  {}", breakdown.join(", "))?;
     }
 
+    if options.case_report {
+        let deviations = result.case_deviations();
+        if deviations.is_empty() {
+            writeln!(writer, "  Case deviations: none")?;
+        } else {
+            let breakdown: Vec<String> = deviations
+                .iter()
+                .map(|(tag, count)| format!("{}: {}", tag, group_thousands(*count)))
+                .collect();
+            writeln!(writer, "  Case deviations: {}", breakdown.join(", "))?;
+        }
+    }
+
     Ok(())
 }
+
+/// Only the numbers of a scan, one per line and without separators so they
+/// are easy to read from scripts: the totals, then each tag by count.
+pub fn print_count<W: Write>(writer: &mut W, summary: &ScanSummary) -> io::Result<()> {
+    writeln!(writer, "Total: {}", summary.total_count)?;
+    writeln!(writer, "Files with TODOs: {}", summary.files_with_todos)?;
+    writeln!(writer, "Files scanned: {}", summary.files_scanned)?;
+    for (tag, count) in summary.tags_by_count() {
+        writeln!(writer, "{}: {}", tag, count)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TodoParser;
+    use std::path::PathBuf;
+
+    #[test]
+    fn case_report_counts_non_canonical_spellings() {
+        let parser = TodoParser::new(&["TODO".to_string()], false);
+        let items = parser.parse_content("// todo: one\n// TODO: two\n// todo: three");
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(PathBuf::from("/repo/lib.rs"), items);
+        let options = PrintOptions {
+            colored: false,
+            case_report: true,
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        print_summary(&mut out, &result, &options).unwrap();
+        let output = String::from_utf8(out).unwrap();
+
+        assert_eq!(result.summary.tag_counts["TODO"], 3);
+        assert!(output.contains("  TODO: 3\n"), "{}", output);
+        assert!(
+            output.ends_with("  Case deviations: TODO: 2\n"),
+            "{}",
+            output
+        );
+    }
+
+    #[test]
+    fn count_prints_totals_then_tags_by_count() {
+        let parser = TodoParser::new(
+            &["TODO".to_string(), "FIXME".to_string(), "BUG".to_string()],
+            true,
+        );
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(
+            PathBuf::from("/repo/a.rs"),
+            parser.parse_content("// TODO: one\n// FIXME: two\n// TODO: three"),
+        );
+        result.add_file(
+            PathBuf::from("/repo/b.rs"),
+            parser.parse_content("// BUG: four\n// TODO: five"),
+        );
+        result.add_file(PathBuf::from("/repo/c.rs"), Vec::new());
+
+        let mut out = Vec::new();
+        print_count(&mut out, &result.summary).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Total: 5\n\
+             Files with TODOs: 2\n\
+             Files scanned: 3\n\
+             TODO: 3\n\
+             BUG: 1\n\
+             FIXME: 1\n"
+        );
+    }
+
+    #[test]
+    fn large_counts_get_thousands_separators() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.summary.total_count = 1234;
+        result.summary.files_with_todos = 56;
+        result.summary.files_scanned = 1_000_000;
+        result.summary.tag_counts.insert("TODO".to_string(), 1234);
+        let options = PrintOptions {
+            colored: false,
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        print_summary(&mut out, &result, &options).unwrap();
+        let output = String::from_utf8(out).unwrap();
+
+        assert_eq!(
+            output,
+            "Found 1,234 TODO items in 56 files (1,000,000 files scanned)\n  TODO: 1,234\n"
+        );
+        assert_eq!(group_thousands(0), "0");
+        assert_eq!(group_thousands(999), "999");
+        assert_eq!(group_thousands(12345), "12,345");
+    }
+}
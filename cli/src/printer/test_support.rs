@@ -0,0 +1,51 @@
+//! Fixtures shared by the printer tests.
+
+use super::options::PrintOptions;
+use std::io;
+use std::path::PathBuf;
+use todo_tree_core::{Priority, ScanResult, TodoItem};
+
+/// An item at `line`, column 4, with the tag's default priority.
+pub fn item(tag: &str, message: &str, line: usize) -> TodoItem {
+    item_at(tag, message, line, 4)
+}
+
+pub fn item_at(tag: &str, message: &str, line: usize, column: usize) -> TodoItem {
+    TodoItem {
+        tag: tag.to_string(),
+        message: message.to_string(),
+        line,
+        column,
+        priority: Priority::from_tag(tag),
+        ..Default::default()
+    }
+}
+
+/// A result rooted at `root`, with `files` given relative to it.
+pub fn result_in(root: &str, files: Vec<(&str, Vec<TodoItem>)>) -> ScanResult {
+    let mut result = ScanResult::new(PathBuf::from(root));
+    for (path, items) in files {
+        result.add_file(PathBuf::from(root).join(path), items);
+    }
+    result
+}
+
+/// Options printing paths relative to `root`.
+pub fn options_in(root: &str) -> PrintOptions {
+    PrintOptions {
+        base_path: Some(PathBuf::from(root)),
+        ..Default::default()
+    }
+}
+
+/// Output of `print` for `result`, with paths relative to its root.
+pub fn render<F>(print: F, result: &ScanResult) -> String
+where
+    F: FnOnce(&mut Vec<u8>, &ScanResult, &PrintOptions) -> io::Result<()>,
+{
+    let root = result.root.as_deref().and_then(|root| root.to_str());
+    let options = root.map(options_in).unwrap_or_default();
+    let mut out = Vec::new();
+    print(&mut out, result, &options).unwrap();
+    String::from_utf8(out).unwrap()
+}
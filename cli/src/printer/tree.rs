@@ -1,5 +1,8 @@
 use super::options::PrintOptions;
-use super::utils::{colorize_tag, format_path, make_clickable_link, make_line_link};
+use super::utils::{
+    blame_suffix, colorize_tag, description_suffix, duplicates_suffix, format_path,
+    make_clickable_link, make_line_link, split_head_tail, terminal_safe,
+};
 use colored::Colorize;
 use std::collections::HashMap;
 use std::io::{self, Write};
@@ -38,16 +41,53 @@ fn print_tree_by_file<W: Write>(
         let is_last_file = idx == total_files - 1;
         print_file_header(writer, path, items.len(), is_last_file, options)?;
 
-        let total_items = items.len();
-        for (item_idx, item) in items.iter().enumerate() {
-            let is_last_item = item_idx == total_items - 1;
-            print_tree_item(writer, item, is_last_file, is_last_item, path, options)?;
+        let (head, elided, tail) = split_head_tail(items, options.head, options.tail);
+        let total_rows = head.len() + usize::from(elided > 0) + tail.len();
+        let mut row = 0;
+
+        for item in head {
+            row += 1;
+            print_tree_item(writer, item, is_last_file, row == total_rows, path, options)?;
+        }
+
+        if elided > 0 {
+            row += 1;
+            print_elided_items(writer, elided, is_last_file, row == total_rows, options)?;
+        }
+
+        for item in tail {
+            row += 1;
+            print_tree_item(writer, item, is_last_file, row == total_rows, path, options)?;
         }
     }
 
     Ok(())
 }
 
+fn print_elided_items<W: Write>(
+    writer: &mut W,
+    count: usize,
+    is_last_file: bool,
+    is_last_item: bool,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    let tree_prefix = if is_last_file { "    " } else { "│   " };
+    let item_prefix = if is_last_item {
+        "└──"
+    } else {
+        "├──"
+    };
+
+    let note = format!("… {} more", count);
+    let note = if options.colored {
+        note.dimmed().to_string()
+    } else {
+        note
+    };
+
+    writeln!(writer, "{}{} {}", tree_prefix, item_prefix, note)
+}
+
 fn print_tree_by_tag<W: Write>(
     writer: &mut W,
     result: &ScanResult,
@@ -101,7 +141,7 @@ fn print_tree_by_tag<W: Write>(
                 item_prefix,
                 link.unwrap_or_else(|| display_path.to_string()),
                 item.line.to_string().cyan(),
-                item.message.dimmed()
+                terminal_safe(&item.message).dimmed()
             )?;
         }
     }
@@ -167,17 +207,24 @@ fn print_tree_item<W: Write>(
         line_num
     };
 
-    let author_str = item
-        .author
-        .as_ref()
-        .map(|a| format!("({})", a))
-        .unwrap_or_default();
+    let author_str = item.format_author();
+    let description = format!(
+        "{}{}{}",
+        description_suffix(&item.tag, options),
+        duplicates_suffix(path, item, options),
+        blame_suffix(item, options)
+    );
 
     if author_str.is_empty() {
         writeln!(
             writer,
-            "{}{} [{}] {}: {}",
-            tree_prefix, item_prefix, line_display, tag, item.message
+            "{}{} [{}] {}: {}{}",
+            tree_prefix,
+            item_prefix,
+            line_display,
+            tag,
+            terminal_safe(&item.message),
+            description
         )?;
     } else {
         let author_display = if options.colored {
@@ -187,10 +234,115 @@ fn print_tree_item<W: Write>(
         };
         writeln!(
             writer,
-            "{}{} [{}] {} {}: {}",
-            tree_prefix, item_prefix, line_display, tag, author_display, item.message
+            "{}{} [{}] {} {}: {}{}",
+            tree_prefix,
+            item_prefix,
+            line_display,
+            tag,
+            author_display,
+            terminal_safe(&item.message),
+            description
         )?;
     }
 
+    let body_prefix = if is_last_item { "    " } else { "│   " };
+    for line in &item.body {
+        let line = terminal_safe(line);
+        let line = if options.colored {
+            line.dimmed().to_string()
+        } else {
+            line.into_owned()
+        };
+        writeln!(writer, "{}{}    {}", tree_prefix, body_prefix, line)?;
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use todo_tree_core::Priority;
+
+    fn item(line: usize) -> TodoItem {
+        TodoItem {
+            tag: "TODO".to_string(),
+            message: format!("item {}", line),
+            line,
+            column: 1,
+            priority: Priority::Medium,
+            ..Default::default()
+        }
+    }
+
+    fn plain_options() -> PrintOptions {
+        PrintOptions {
+            colored: false,
+            clickable_links: false,
+            base_path: Some(PathBuf::from("/repo")),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn escape_sequences_in_messages_are_neutralized() {
+        let mut hostile = item(1);
+        hostile.message = "looks fine\x1b[2K\rTODO: spoofed".to_string();
+        hostile.body = vec!["\x1b]8;;https://evil.example\x1b\\link".to_string()];
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(PathBuf::from("/repo/a.rs"), vec![hostile]);
+
+        let mut out = Vec::new();
+        print_tree(&mut out, &result, &plain_options()).unwrap();
+        let output = String::from_utf8(out).unwrap();
+
+        assert!(!output.contains('\x1b'), "{:?}", output);
+        assert!(!output.contains('\r'), "{:?}", output);
+        assert!(output.contains("looks fine\\u{1b}[2K\\u{d}TODO: spoofed"));
+    }
+
+    #[test]
+    fn head_and_tail_show_only_the_ends_of_large_files() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(PathBuf::from("/repo/big.rs"), (1..=10).map(item).collect());
+
+        let options = PrintOptions {
+            head: Some(2),
+            tail: Some(2),
+            ..plain_options()
+        };
+        let mut out = Vec::new();
+        print_tree(&mut out, &result, &options).unwrap();
+        let output = String::from_utf8(out).unwrap();
+
+        assert_eq!(
+            output,
+            "\
+└── big.rs (10)
+    ├── [L1] TODO: item 1
+    ├── [L2] TODO: item 2
+    ├── … 6 more
+    ├── [L9] TODO: item 9
+    └── [L10] TODO: item 10
+"
+        );
+    }
+
+    #[test]
+    fn head_and_tail_covering_every_item_elide_nothing() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(PathBuf::from("/repo/small.rs"), (1..=3).map(item).collect());
+
+        let options = PrintOptions {
+            head: Some(2),
+            tail: Some(2),
+            ..plain_options()
+        };
+        let mut out = Vec::new();
+        print_tree(&mut out, &result, &options).unwrap();
+        let output = String::from_utf8(out).unwrap();
+
+        assert!(!output.contains('…'));
+        assert_eq!(output.lines().count(), 4);
+    }
+}
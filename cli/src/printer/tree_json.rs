@@ -0,0 +1,139 @@
+use super::ascii_tree::PathNode;
+use super::json::{JsonSummary, JsonTodoItem};
+use super::options::PrintOptions;
+use super::utils::format_path;
+use serde::Serialize;
+use std::io::{self, Write};
+use todo_tree_core::ScanResult;
+
+#[derive(Debug, Serialize)]
+pub struct JsonTreeOutput {
+    pub tree: JsonTreeNode,
+    pub summary: JsonSummary,
+}
+
+/// A directory or file of the tree view; items sit on file nodes.
+#[derive(Debug, Serialize)]
+pub struct JsonTreeNode {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// Display path of a file node, as in the flat JSON output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Items in this node and everything below it.
+    pub count: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<JsonTreeNode>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<JsonTodoItem>,
+}
+
+pub fn print_tree_json<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    let output = JsonTreeOutput::from_scan_result(result, options);
+    let json_str = serde_json::to_string_pretty(&output).map_err(io::Error::other)?;
+    writeln!(writer, "{}", json_str)?;
+    Ok(())
+}
+
+impl JsonTreeOutput {
+    pub fn from_scan_result(result: &ScanResult, options: &PrintOptions) -> Self {
+        let root = PathNode::build(result, options);
+        Self {
+            tree: JsonTreeNode::from_node(String::new(), &root, result, options),
+            summary: JsonSummary::from_scan_result(result),
+        }
+    }
+}
+
+impl JsonTreeNode {
+    fn from_node(
+        name: String,
+        node: &PathNode,
+        result: &ScanResult,
+        options: &PrintOptions,
+    ) -> Self {
+        let path = node.path.filter(|_| !node.is_dir());
+        Self {
+            name,
+            kind: if path.is_some() { "file" } else { "directory" },
+            path: path.map(|path| format_path(path, options)),
+            count: node.count(),
+            children: node
+                .children
+                .iter()
+                .map(|(name, child)| Self::from_node(name.clone(), child, result, options))
+                .collect(),
+            items: match node.path {
+                Some(path) => node
+                    .items
+                    .iter()
+                    .map(|item| JsonTodoItem::new(result, path, item, options))
+                    .collect(),
+                None => Vec::new(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use todo_tree_core::TodoItem;
+
+    fn items(count: usize) -> Vec<TodoItem> {
+        (1..=count)
+            .map(|line| TodoItem {
+                tag: "TODO".to_string(),
+                message: format!("item {}", line),
+                line,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nests_directories_with_items_on_files() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(PathBuf::from("/repo/src/main.rs"), items(2));
+        result.add_file(PathBuf::from("/repo/src/cli/args.rs"), items(1));
+        result.add_file(PathBuf::from("/repo/README.md"), items(1));
+        let options = PrintOptions {
+            base_path: Some(PathBuf::from("/repo")),
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        print_tree_json(&mut out, &result, &options).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+        let root = &json["tree"];
+        assert_eq!(root["type"], "directory");
+        assert_eq!(root["count"], 4);
+        assert_eq!(root["children"][0]["name"], "README.md");
+        assert_eq!(root["children"][0]["type"], "file");
+
+        let src = &root["children"][1];
+        assert_eq!(src["name"], "src");
+        assert_eq!(src["type"], "directory");
+        assert_eq!(src["count"], 3);
+        assert!(src.get("items").is_none());
+
+        let cli = &src["children"][0];
+        assert_eq!(cli["name"], "cli");
+        let args = &cli["children"][0];
+        assert_eq!(args["type"], "file");
+        assert_eq!(args["path"], "src/cli/args.rs");
+        assert_eq!(args["items"].as_array().unwrap().len(), 1);
+
+        let main = &src["children"][1];
+        assert_eq!(main["name"], "main.rs");
+        assert_eq!(main["items"][1]["message"], "item 2");
+        assert_eq!(json["summary"]["total_count"], 4);
+    }
+}
@@ -0,0 +1,75 @@
+use super::options::PrintOptions;
+use super::utils::format_path;
+use std::io::{self, Write};
+use todo_tree_core::ScanResult;
+
+const HEADER: &str = "file\tline\tcolumn\ttag\tpriority\tauthor\tmessage";
+
+pub fn print_tsv<W: Write>(
+    writer: &mut W,
+    result: &ScanResult,
+    options: &PrintOptions,
+) -> io::Result<()> {
+    writeln!(writer, "{}", HEADER)?;
+
+    let mut all_items = result.all_items();
+    all_items.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.line.cmp(&b.1.line)));
+
+    for (path, item) in all_items {
+        let fields = [
+            sanitize_field(&format_path(&path, options)),
+            item.line.to_string(),
+            item.column.to_string(),
+            sanitize_field(&item.tag),
+            item.priority.to_string(),
+            sanitize_field(&item.authors.join(",")),
+            sanitize_field(&item.message),
+        ];
+        writeln!(writer, "{}", fields.join("\t"))?;
+    }
+
+    Ok(())
+}
+
+/// TSV has no quoting, so tabs and line breaks inside a field become spaces.
+fn sanitize_field(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::test_support::{item, render, result_in};
+
+    #[test]
+    fn prints_header_and_one_row_per_item() {
+        let result = result_in(
+            "/repo",
+            vec![(
+                "src/main.rs",
+                vec![item("TODO", "first", 3), item("BUG", "second", 9)],
+            )],
+        );
+
+        let output = render(print_tsv, &result);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[0], HEADER);
+        assert_eq!(lines[1], "src/main.rs\t3\t4\tTODO\tMedium\t\tfirst");
+        assert_eq!(lines[2], "src/main.rs\t9\t4\tBUG\tCritical\t\tsecond");
+    }
+
+    #[test]
+    fn embedded_tabs_and_newlines_are_replaced_with_spaces() {
+        let result = result_in(
+            "/repo",
+            vec![("lib.rs", vec![item("TODO", "split\there\nand there", 1)])],
+        );
+
+        let output = render(print_tsv, &result);
+        let row = output.lines().nth(1).unwrap();
+
+        assert_eq!(row.split('\t').count(), 7);
+        assert!(row.ends_with("\tsplit here and there"));
+    }
+}
@@ -1,11 +1,14 @@
 use super::options::PrintOptions;
 use crate::utils::display::priority_to_color;
+use crate::utils::time::humanize_since;
+use chrono::Utc;
 use colored::Colorize;
+use std::borrow::Cow;
 use std::path::Path;
-use todo_tree_core::Priority;
+use todo_tree_core::{Priority, TodoItem};
 
 pub fn format_path(path: &Path, options: &PrintOptions) -> String {
-    if options.full_paths {
+    let display = if options.full_paths {
         path.display().to_string()
     } else if let Some(base) = &options.base_path {
         path.strip_prefix(base)
@@ -13,9 +16,20 @@ pub fn format_path(path: &Path, options: &PrintOptions) -> String {
             .unwrap_or_else(|_| path.display().to_string())
     } else {
         path.display().to_string()
+    };
+
+    if options.posix_paths {
+        to_posix_separators(&display)
+    } else {
+        display
     }
 }
 
+/// Replace Windows `\` separators with `/`. Only used for displayed paths.
+pub fn to_posix_separators(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
 pub fn make_clickable_link(path: &Path, line: usize, options: &PrintOptions) -> Option<String> {
     if !options.clickable_links || !supports_hyperlinks() {
         return None;
@@ -60,6 +74,107 @@ pub fn make_line_link(path: &Path, line: usize, options: &PrintOptions) -> Optio
     Some(link)
 }
 
+/// Split a file's items into the leading and trailing slices selected by
+/// `head`/`tail`, along with how many items are elided between them.
+pub fn split_head_tail<T>(
+    items: &[T],
+    head: Option<usize>,
+    tail: Option<usize>,
+) -> (&[T], usize, &[T]) {
+    if head.is_none() && tail.is_none() {
+        return (items, 0, &[]);
+    }
+
+    let head = head.unwrap_or(0).min(items.len());
+    let tail = tail.unwrap_or(0).min(items.len() - head);
+    let elided = items.len() - head - tail;
+
+    (&items[..head], elided, &items[items.len() - tail..])
+}
+
+/// The description of `tag`, when descriptions were requested and it has one.
+pub fn tag_description<'a>(tag: &str, options: &'a PrintOptions) -> Option<&'a str> {
+    let description = options
+        .descriptions
+        .as_ref()?
+        .find(tag)?
+        .description
+        .as_str();
+    (!description.is_empty()).then_some(description)
+}
+
+/// ` (description)` to append after an item's message, or nothing.
+pub fn description_suffix(tag: &str, options: &PrintOptions) -> String {
+    match tag_description(tag, options) {
+        Some(description) if options.colored => format!(" ({})", description).dimmed().to_string(),
+        Some(description) => format!(" ({})", description),
+        None => String::new(),
+    }
+}
+
+/// ` (seen in N files)` for an item standing in for duplicates, or nothing.
+pub fn duplicates_suffix(path: &Path, item: &TodoItem, options: &PrintOptions) -> String {
+    if item.duplicates.is_empty() {
+        return String::new();
+    }
+
+    let suffix = format!(" (seen in {} files)", item.seen_in_files(path));
+    if options.colored {
+        suffix.dimmed().to_string()
+    } else {
+        suffix
+    }
+}
+
+/// ` (alice, 3 months ago)` for an item with `--blame` data, or nothing.
+pub fn blame_suffix(item: &TodoItem, options: &PrintOptions) -> String {
+    let Some(author) = &item.blame_author else {
+        return String::new();
+    };
+
+    let suffix = match item.blame_date {
+        Some(date) => format!(" ({}, {})", author, humanize_since(date, Utc::now())),
+        None => format!(" ({})", author),
+    };
+    if options.colored {
+        suffix.dimmed().to_string()
+    } else {
+        suffix
+    }
+}
+
+/// `text` with control characters (ANSI escapes, `\r`, ...) and invisible
+/// formatting characters (zero-width spaces, bidi overrides) written as
+/// `\u{..}` escapes, so scanned text can neither restyle the terminal nor hide
+/// what it says. Tabs are kept.
+pub fn terminal_safe(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_unsafe_for_terminal) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut safe = String::with_capacity(text.len());
+    for c in text.chars() {
+        if is_unsafe_for_terminal(c) {
+            safe.extend(c.escape_unicode());
+        } else {
+            safe.push(c);
+        }
+    }
+    Cow::Owned(safe)
+}
+
+fn is_unsafe_for_terminal(c: char) -> bool {
+    (c.is_control() && c != '\t')
+        || matches!(
+            c,
+            '\u{200B}'..='\u{200F}'
+                | '\u{202A}'..='\u{202E}'
+                | '\u{2060}'..='\u{2064}'
+                | '\u{2066}'..='\u{2069}'
+                | '\u{FEFF}'
+        )
+}
+
 pub fn colorize_tag(tag: &str, options: &PrintOptions) -> String {
     if !options.colored {
         return tag.to_string();
@@ -102,3 +217,58 @@ fn supports_hyperlinks() -> bool {
 
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use todo_tree_core::TagRegistry;
+
+    #[test]
+    fn terminal_safe_escapes_controls_and_invisible_characters() {
+        assert!(matches!(terminal_safe("plain\ttext"), Cow::Borrowed(_)));
+        assert_eq!(
+            terminal_safe("fix \x1b[31mred\x1b[0m\rover"),
+            "fix \\u{1b}[31mred\\u{1b}[0m\\u{d}over"
+        );
+        assert_eq!(
+            terminal_safe("a\u{200B}b\u{202E}c"),
+            "a\\u{200b}b\\u{202e}c"
+        );
+    }
+
+    #[test]
+    fn posix_separators_replace_backslashes() {
+        assert_eq!(to_posix_separators(r"src\cli\args.rs"), "src/cli/args.rs");
+        assert_eq!(to_posix_separators("src/lib.rs"), "src/lib.rs");
+    }
+
+    #[test]
+    fn descriptions_come_from_the_registry_only_when_enabled() {
+        let mut options = PrintOptions {
+            colored: false,
+            ..Default::default()
+        };
+        assert_eq!(description_suffix("TODO", &options), "");
+
+        options.descriptions = Some(TagRegistry::default());
+        assert_eq!(
+            description_suffix("TODO", &options),
+            " (General TODO items)"
+        );
+        assert_eq!(tag_description("UNKNOWN", &options), None);
+    }
+
+    #[test]
+    fn format_path_normalizes_only_when_enabled() {
+        let path = PathBuf::from(r"/repo/src\lib.rs");
+        let mut options = PrintOptions {
+            base_path: Some(PathBuf::from("/repo")),
+            ..Default::default()
+        };
+
+        assert_eq!(format_path(&path, &options), r"src\lib.rs");
+        options.posix_paths = true;
+        assert_eq!(format_path(&path, &options), "src/lib.rs");
+    }
+}
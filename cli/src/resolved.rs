@@ -0,0 +1,231 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs::OpenOptions;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use todo_tree_core::ScanResult;
+
+/// File in the scan root listing the stable ids of items marked resolved.
+pub const RESOLVED_FILE: &str = ".todo-resolved";
+
+/// Read the resolved ids under `root`, one per line. Blank lines and `#`
+/// comments are ignored, and a missing file means nothing is resolved.
+pub fn load_resolved(root: &Path) -> Result<HashSet<String>> {
+    let path = root.join(RESOLVED_FILE);
+    if !path.exists() {
+        return Ok(HashSet::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// First words of a message saying the work is already done, e.g.
+/// `TODO: done` or `FIXME: fixed in #12`.
+const RESOLVED_WORDS: [&str; 4] = ["done", "fixed", "resolved", "obsolete"];
+
+/// Whether an item's message says it no longer needs doing.
+pub fn looks_resolved(message: &str) -> bool {
+    message
+        .split(|c: char| !c.is_alphanumeric())
+        .find(|word| !word.is_empty())
+        .is_some_and(|word| RESOLVED_WORDS.iter().any(|w| word.eq_ignore_ascii_case(w)))
+}
+
+/// Items `--format patch` should delete: those listed in the resolved file
+/// under `root`, and those whose message looks resolved.
+pub fn patch_candidates(result: &ScanResult, root: &Path) -> Result<ScanResult> {
+    let resolved = load_resolved(root)?;
+    Ok(result.filter_items(|path, item| {
+        resolved.contains(&result.item_id(path, item)) || looks_resolved(&item.message)
+    }))
+}
+
+/// Append `ids` to the resolved file under `root`, creating it if needed.
+pub fn append_resolved(root: &Path, ids: &[String]) -> Result<()> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let path = root.join(RESOLVED_FILE);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+
+    for id in ids {
+        writeln!(file, "{}", id)?;
+    }
+
+    Ok(())
+}
+
+/// Ask about each item in turn and return the ids the user marked resolved.
+///
+/// Answers are `y` (resolve), `n` or empty (keep), and `q` (stop asking).
+pub fn prompt_resolve<R: BufRead, W: Write>(
+    result: &ScanResult,
+    root: &Path,
+    input: &mut R,
+    output: &mut W,
+) -> Result<Vec<String>> {
+    let mut resolved = Vec::new();
+
+    for (path, item) in result.sorted_items(Default::default()) {
+        let display_path = path.strip_prefix(root).unwrap_or(&path);
+        write!(
+            output,
+            "{}:{} [{}] {}\nMark resolved? [y/N/q] ",
+            display_path.display(),
+            item.line,
+            item.tag,
+            item.message
+        )?;
+        output.flush()?;
+
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            break;
+        }
+
+        match answer.trim().to_ascii_lowercase().as_str() {
+            "y" | "yes" => resolved.push(result.item_id(&path, &item)),
+            "q" | "quit" => break,
+            _ => {}
+        }
+    }
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use todo_tree_core::TodoItem;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("todo_resolved_{name}_{unique}"));
+        std::fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn result(root: &Path) -> ScanResult {
+        let item = |message: &str, line| TodoItem {
+            tag: "TODO".to_string(),
+            message: message.to_string(),
+            line,
+            ..Default::default()
+        };
+        let mut result = ScanResult::new(root.to_path_buf());
+        result.add_file(root.join("lib.rs"), vec![item("keep", 1), item("done", 2)]);
+        result
+    }
+
+    #[test]
+    fn resolved_ids_hide_their_items() {
+        let root = temp_root("hide");
+        let result = result(&root);
+
+        let mut input = "n\ny\n".as_bytes();
+        let mut output = Vec::new();
+        let ids = prompt_resolve(&result, &root, &mut input, &mut output).unwrap();
+        append_resolved(&root, &ids).unwrap();
+
+        let resolved = load_resolved(&root).unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+        let visible = result.without_ids(&resolved);
+
+        assert_eq!(ids.len(), 1);
+        let items = visible.all_items();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].1.message, "keep");
+        assert_eq!(visible.summary.total_count, 1);
+    }
+
+    #[test]
+    fn resolving_one_of_two_identical_items_keeps_the_other() {
+        let root = temp_root("identical");
+        let item = |line| TodoItem {
+            tag: "TODO".to_string(),
+            message: "same words".to_string(),
+            line,
+            ..Default::default()
+        };
+        let mut result = ScanResult::new(root.clone());
+        result.add_file(root.join("lib.rs"), vec![item(3), item(8)]);
+
+        let mut input = "y\nn\n".as_bytes();
+        let mut output = Vec::new();
+        let ids = prompt_resolve(&result, &root, &mut input, &mut output).unwrap();
+        append_resolved(&root, &ids).unwrap();
+
+        let resolved = load_resolved(&root).unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+        let visible = result.without_ids(&resolved);
+
+        let items = visible.all_items();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].1.line, 8);
+        assert_ne!(result.fingerprint(), visible.fingerprint());
+    }
+
+    #[test]
+    fn identical_items_can_be_resolved_in_separate_runs() {
+        let root = temp_root("separate_runs");
+        let item = |line| TodoItem {
+            tag: "TODO".to_string(),
+            message: "x".to_string(),
+            line,
+            ..Default::default()
+        };
+        let mut result = ScanResult::new(root.clone());
+        result.add_file(root.join("lib.rs"), vec![item(3), item(8)]);
+        result.assign_ids();
+
+        let mut output = Vec::new();
+        for _ in 0..2 {
+            let visible = result.without_ids(&load_resolved(&root).unwrap());
+            let ids = prompt_resolve(&visible, &root, &mut "y\n".as_bytes(), &mut output).unwrap();
+            append_resolved(&root, &ids).unwrap();
+        }
+
+        let resolved = load_resolved(&root).unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(resolved.len(), 2);
+        assert!(result.without_ids(&resolved).is_empty());
+    }
+
+    #[test]
+    fn missing_file_resolves_nothing() {
+        let root = temp_root("missing");
+        let resolved = load_resolved(&root).unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert!(resolved.is_empty());
+    }
+
+    #[test]
+    fn done_and_fixed_messages_look_resolved() {
+        assert!(looks_resolved("done"));
+        assert!(looks_resolved("Fixed in #12"));
+        assert!(looks_resolved("(resolved) keep for now"));
+        assert!(!looks_resolved("make this done faster"));
+        assert!(!looks_resolved("donezo"));
+        assert!(!looks_resolved(""));
+    }
+}
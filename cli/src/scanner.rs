@@ -1,8 +1,14 @@
+use crate::cache::CachedScan;
 use crate::parser::TodoParser;
-use anyhow::{Context, Result};
-use ignore::WalkBuilder;
-use ignore::overrides::OverrideBuilder;
-use std::path::Path;
+use anyhow::{Context, Result, bail};
+use ignore::gitignore::GitignoreBuilder;
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::{DirEntry, Match, WalkBuilder, WalkState};
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, mpsc};
 use todo_tree_core::{ScanResult, TodoItem};
 
 #[derive(Debug, Clone)]
@@ -14,6 +20,20 @@ pub struct ScanOptions {
     pub hidden: bool,
     pub threads: usize,
     pub respect_gitignore: bool,
+    /// Skip paths matched by `.todoignore` files, which use gitignore syntax
+    /// and apply even when `respect_gitignore` is off.
+    pub respect_todoignore: bool,
+    /// Only read files with one of these extensions (without the dot). Empty means all files.
+    pub extensions: Vec<String>,
+    /// Record directories and files skipped for lack of permission in
+    /// [`ScanResult::errors`] instead of dropping them silently.
+    pub warn_unreadable: bool,
+    /// Report symlinked paths given to the scan under the link rather than its
+    /// resolved target. The target's content is scanned either way.
+    pub keep_link_paths: bool,
+    /// Stop walking once this many items are collected. Which items are kept
+    /// then depends on walk order.
+    pub max_results: Option<usize>,
 }
 
 impl Default for ScanOptions {
@@ -26,10 +46,103 @@ impl Default for ScanOptions {
             hidden: false,
             threads: 0,
             respect_gitignore: true,
+            respect_todoignore: true,
+            extensions: Vec::new(),
+            warn_unreadable: false,
+            keep_link_paths: false,
+            max_results: None,
         }
     }
 }
 
+/// Chainable construction of [`ScanOptions`], starting from its defaults.
+///
+/// ```
+/// use todo_tree::scanner::ScanOptionsBuilder;
+///
+/// let options = ScanOptionsBuilder::new().max_depth(3).hidden(true).build();
+/// assert_eq!(options.max_depth, 3);
+/// assert!(options.hidden);
+/// assert!(options.respect_gitignore);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptionsBuilder {
+    options: ScanOptions,
+}
+
+impl ScanOptionsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add glob patterns a file must match to be scanned.
+    pub fn include<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options
+            .include
+            .extend(patterns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Add glob patterns for files to skip.
+    pub fn exclude<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.options
+            .exclude
+            .extend(patterns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Maximum directory depth; 0 means unlimited.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.options.max_depth = max_depth;
+        self
+    }
+
+    pub fn follow_links(mut self, follow_links: bool) -> Self {
+        self.options.follow_links = follow_links;
+        self
+    }
+
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.options.hidden = hidden;
+        self
+    }
+
+    /// Walker threads; 0 lets the walker choose.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.options.threads = threads;
+        self
+    }
+
+    pub fn respect_gitignore(mut self, respect_gitignore: bool) -> Self {
+        self.options.respect_gitignore = respect_gitignore;
+        self
+    }
+
+    pub fn build(self) -> ScanOptions {
+        self.options
+    }
+}
+
+/// Tool-specific ignore file, honored in the scan root and any subdirectory.
+pub const TODOIGNORE_FILE: &str = ".todoignore";
+
+/// Per-directory rules file of `include <glob>` and `exclude <glob>` lines,
+/// applied to the subtree it sits in on top of the configured globs.
+pub const RULES_FILE: &str = ".todo-tree";
+
+/// Path argument that reads from stdin instead of the filesystem.
+pub const STDIN_ARG: &str = "-";
+/// Path that items read from stdin are reported under.
+pub const STDIN_PATH: &str = "<stdin>";
+
 pub struct Scanner {
     parser: TodoParser,
     options: ScanOptions,
@@ -40,84 +153,1042 @@ impl Scanner {
         Self { parser, options }
     }
 
-    pub fn scan(&self, root: &Path) -> Result<ScanResult> {
-        let root = root
-            .canonicalize()
-            .with_context(|| format!("Failed to resolve path: {}", root.display()))?;
-
-        let mut result = ScanResult::new(root.clone());
-        let mut builder = WalkBuilder::new(&root);
+    /// Directory that paths from a scan of `paths` are shown relative to: the
+    /// directory itself for a single directory, otherwise the current directory.
+    pub fn common_root(paths: &[PathBuf], keep_link_paths: bool) -> Result<PathBuf> {
+        if let [path] = paths
+            && path.is_dir()
+        {
+            return resolve(path, keep_link_paths);
+        }
 
-        builder
-            .hidden(!self.options.hidden)
-            .follow_links(self.options.follow_links)
-            .git_ignore(self.options.respect_gitignore)
-            .git_global(self.options.respect_gitignore)
-            .git_exclude(self.options.respect_gitignore);
+        std::env::current_dir()
+            .and_then(|dir| dir.canonicalize())
+            .context("Failed to resolve the current directory")
+    }
 
-        if self.options.max_depth > 0 {
-            builder.max_depth(Some(self.options.max_depth));
+    /// Scan several directories and files into one result rooted at
+    /// [`Scanner::common_root`]. Files are parsed directly without a walk, and
+    /// [`STDIN_ARG`] reads stdin, reported as [`STDIN_PATH`].
+    pub fn scan_many(&self, paths: &[PathBuf]) -> Result<ScanResult> {
+        let root = Self::common_root(paths, self.options.keep_link_paths)?;
+        if let [path] = paths
+            && path.is_dir()
+        {
+            return self.scan(path);
         }
 
-        if self.options.threads > 0 {
-            builder.threads(self.options.threads);
-        }
+        let mut seen = HashSet::new();
+        let mut result = ScanResult::new(root);
+        for path in paths {
+            let remaining = self
+                .options
+                .max_results
+                .map(|max| max.saturating_sub(result.summary.total_count));
+            if remaining == Some(0) {
+                break;
+            }
 
-        if !self.options.include.is_empty() || !self.options.exclude.is_empty() {
-            let mut override_builder = OverrideBuilder::new(&root);
-            for pattern in &self.options.include {
-                override_builder
-                    .add(pattern)
-                    .with_context(|| format!("Invalid include pattern: {}", pattern))?;
+            if path.as_os_str() == STDIN_ARG {
+                let mut items = self
+                    .parser
+                    .parse_reader(std::io::stdin().lock())
+                    .context("Failed to read stdin")?;
+                items.truncate(remaining.unwrap_or(usize::MAX));
+                let mut single = ScanResult::new(result.root.clone().unwrap_or_default());
+                single.add_file(PathBuf::from(STDIN_PATH), items);
+                result.merge(single);
+                continue;
             }
 
-            for pattern in &self.options.exclude {
-                let exclude_pattern = format!("!{}", pattern);
-                override_builder
-                    .add(&exclude_pattern)
-                    .with_context(|| format!("Invalid exclude pattern: {}", pattern))?;
+            if !seen.insert(resolve(path, false)?) {
+                continue;
             }
+            let path = resolve(path, self.options.keep_link_paths)?;
 
-            let overrides = override_builder.build()?;
-            builder.overrides(overrides);
+            if path.is_dir() {
+                result.merge(self.walk(&path, remaining, None)?);
+            } else {
+                let mut single = ScanResult::new(path.parent().unwrap_or(&path).to_path_buf());
+                match self.parse_file(&path) {
+                    Ok(mut items) => {
+                        items.truncate(remaining.unwrap_or(usize::MAX));
+                        single.add_file(path, items);
+                    }
+                    Err(err) => {
+                        single.summary.files_scanned += 1;
+                        self.record_unreadable(&mut single, &path, &err);
+                    }
+                }
+                result.merge(single);
+            }
         }
 
-        for entry in builder.build() {
-            match entry {
-                Ok(entry) => {
-                    let path = entry.path();
+        result.assign_ids();
+        Ok(result)
+    }
 
-                    if path.is_dir() {
-                        continue;
-                    }
+    pub fn scan(&self, root: &Path) -> Result<ScanResult> {
+        self.walk(root, self.options.max_results, None)
+    }
 
-                    if let Some(file_type) = entry.file_type()
-                        && !file_type.is_file()
-                    {
-                        continue;
-                    }
+    /// Scan `root`, reusing items stored in `cache_path` for files whose
+    /// modification time and size are unchanged, then rewrite the cache.
+    /// The cache is discarded when the parser settings differ.
+    pub fn scan_cached(&self, root: &Path, cache_path: &Path) -> Result<ScanResult> {
+        self.scan_with_cache(root, cache_path)
+            .map(|(result, _)| result)
+    }
+
+    /// [`Scanner::scan_cached`], also returning how many files were reparsed.
+    fn scan_with_cache(&self, root: &Path, cache_path: &Path) -> Result<(ScanResult, usize)> {
+        let cache = CachedScan::load(cache_path, &self.parser.cache_key());
+        let result = self.walk(root, self.options.max_results, Some(&cache))?;
+        let reparsed = cache.reparsed();
+        cache.save()?;
+        Ok((result, reparsed))
+    }
 
-                    match self.parse_file(path) {
-                        Ok(items) => {
-                            result.add_file(path.to_path_buf(), items);
+    /// Walk `root`, quitting early once `limit` items are found.
+    fn walk(
+        &self,
+        root: &Path,
+        limit: Option<usize>,
+        cache: Option<&CachedScan>,
+    ) -> Result<ScanResult> {
+        let root = resolve(root, self.options.keep_link_paths)?;
+
+        let mut result = ScanResult::new(root.clone());
+        let builder = walk_builder(&root, &self.options)?;
+
+        let (sender, receiver) = mpsc::channel();
+        let (error_sender, error_receiver) = mpsc::channel();
+        let found = &AtomicUsize::new(0);
+        let rules = &SubtreeRules::new(&root);
+        let rules_error = &Mutex::new(None);
+        builder.build_parallel().run(|| {
+            let sender = sender.clone();
+            let error_sender = error_sender.clone();
+            Box::new(move |entry| {
+                if limit.is_some_and(|limit| found.load(Ordering::Relaxed) >= limit) {
+                    return WalkState::Quit;
+                }
+
+                match entry {
+                    Ok(entry) => {
+                        if let Some(state) = rules.check(&entry, rules_error) {
+                            return state;
+                        }
+                        if let Some(parsed) = self.visit(&entry, cache) {
+                            if let (_, Ok(items)) = &parsed {
+                                found.fetch_add(items.len(), Ordering::Relaxed);
+                            }
+                            let _ = sender.send(parsed);
                         }
-                        Err(_) => {
-                            result.summary.files_scanned += 1;
+                    }
+                    Err(err) => {
+                        if is_symlink_loop(&err)
+                            || self.options.warn_unreadable && is_permission_denied(&err)
+                        {
+                            let _ = error_sender.send(err.to_string());
                         }
                     }
                 }
-                Err(_) => {
-                    continue;
+                WalkState::Continue
+            })
+        });
+        drop(sender);
+        drop(error_sender);
+        if let Some(err) = rules_error.lock().unwrap().take() {
+            return Err(err);
+        }
+
+        // Threads finish in any order; sort so the result never depends on
+        // scheduling, short of a `limit` cutting the walk off.
+        let mut parsed: Vec<(PathBuf, Result<Vec<TodoItem>>)> = receiver.into_iter().collect();
+        if self.options.follow_links {
+            parsed = one_path_per_file(parsed);
+        }
+        parsed.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (path, items) in parsed {
+            match items {
+                Ok(mut items) => {
+                    if let Some(limit) = limit {
+                        items.truncate(limit.saturating_sub(result.summary.total_count));
+                    }
+                    result.add_file(path, items);
+                }
+                Err(err) => {
+                    result.summary.files_scanned += 1;
+                    self.record_unreadable(&mut result, &path, &err);
                 }
             }
         }
 
+        result.errors.extend(error_receiver);
+        result.errors.sort();
+        result.assign_ids();
+
         Ok(result)
     }
 
+    /// The files a scan of `paths` would parse, through every ignore file,
+    /// glob, rules file and extension filter, without reading any of them.
+    pub fn list_files(&self, paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for path in paths {
+            if path.as_os_str() == STDIN_ARG {
+                files.push(PathBuf::from(STDIN_PATH));
+                continue;
+            }
+            let path = resolve(path, self.options.keep_link_paths)?;
+            if path.is_dir() {
+                files.extend(self.walk_files(&path)?);
+            } else {
+                files.push(path);
+            }
+        }
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+
+    fn walk_files(&self, root: &Path) -> Result<Vec<PathBuf>> {
+        let (sender, receiver) = mpsc::channel();
+        let rules = &SubtreeRules::new(root);
+        let rules_error = &Mutex::new(None);
+        walk_builder(root, &self.options)?.build_parallel().run(|| {
+            let sender = sender.clone();
+            Box::new(move |entry| {
+                let Ok(entry) = entry else {
+                    return WalkState::Continue;
+                };
+                if let Some(state) = rules.check(&entry, rules_error) {
+                    return state;
+                }
+                if self.is_scannable(&entry) {
+                    let _ = sender.send(entry.into_path());
+                }
+                WalkState::Continue
+            })
+        });
+        drop(sender);
+        if let Some(err) = rules_error.lock().unwrap().take() {
+            return Err(err);
+        }
+        Ok(receiver.into_iter().collect())
+    }
+
+    /// Whether a walked entry is a regular file with an allowed extension.
+    fn is_scannable(&self, entry: &DirEntry) -> bool {
+        let path = entry.path();
+        !path.is_dir()
+            && entry.file_type().is_none_or(|t| t.is_file())
+            && self.has_allowed_extension(path)
+    }
+
+    /// Parse one walked entry. Returns `None` for entries that are not scanned
+    /// at all, and `Some((path, Err(_)))` for files that could not be parsed.
+    fn visit(
+        &self,
+        entry: &DirEntry,
+        cache: Option<&CachedScan>,
+    ) -> Option<(PathBuf, Result<Vec<TodoItem>>)> {
+        let path = entry.path();
+
+        if !self.is_scannable(entry) {
+            return None;
+        }
+
+        if let Some(cache) = cache {
+            if cache.is_cache_file(path) {
+                return None;
+            }
+            return Some((
+                path.to_path_buf(),
+                cache.items(path, || self.parse_file(path)),
+            ));
+        }
+
+        Some((path.to_path_buf(), self.parse_file(path)))
+    }
+
+    fn record_unreadable(&self, result: &mut ScanResult, path: &Path, err: &anyhow::Error) {
+        if !self.options.warn_unreadable {
+            return;
+        }
+
+        let denied = err.chain().find_map(|cause| {
+            cause
+                .downcast_ref::<io::Error>()
+                .filter(|io_err| io_err.kind() == io::ErrorKind::PermissionDenied)
+        });
+        if let Some(io_err) = denied {
+            result
+                .errors
+                .push(format!("{}: {}", path.display(), io_err));
+        }
+    }
+
+    fn has_allowed_extension(&self, path: &Path) -> bool {
+        if self.options.extensions.is_empty() {
+            return true;
+        }
+
+        let Some(extension) = path.extension().and_then(|e| e.to_str()) else {
+            return false;
+        };
+
+        self.options.extensions.iter().any(|allowed| {
+            allowed
+                .trim_start_matches('.')
+                .eq_ignore_ascii_case(extension)
+        })
+    }
+
     fn parse_file(&self, path: &Path) -> Result<Vec<TodoItem>> {
         self.parser
             .parse_file(path)
             .with_context(|| format!("Failed to parse file: {}", path.display()))
     }
 }
+
+/// Whether a changed path can affect a scan of some roots, by the same hidden,
+/// include/exclude and ignore-file rules as the walk, without walking.
+/// Changes to ignore and rules files themselves always count.
+pub struct ScanFilter {
+    roots: Vec<(PathBuf, Option<Override>)>,
+    options: ScanOptions,
+}
+
+impl ScanFilter {
+    pub fn new(paths: &[PathBuf], options: &ScanOptions) -> Result<Self> {
+        let mut roots = Vec::new();
+        for path in paths {
+            if path.as_os_str() == STDIN_ARG {
+                continue;
+            }
+            let root = resolve(path, options.keep_link_paths)?;
+            let overrides = overrides(&root, options)?;
+            roots.push((root, overrides));
+        }
+        Ok(Self {
+            roots,
+            options: options.clone(),
+        })
+    }
+
+    /// The resolved paths being scanned, to watch for changes.
+    pub fn roots(&self) -> impl Iterator<Item = &Path> {
+        self.roots.iter().map(|(root, _)| root.as_path())
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        self.roots.iter().any(|(root, overrides)| {
+            path == root
+                || path.starts_with(root) && self.matches_under(root, overrides.as_ref(), path)
+        })
+    }
+
+    fn matches_under(&self, root: &Path, overrides: Option<&Override>, path: &Path) -> bool {
+        let name = path.file_name().and_then(|name| name.to_str());
+        if name.is_some_and(|name| self.ignore_files().contains(&name) || name == RULES_FILE) {
+            return true;
+        }
+
+        let hidden = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .components()
+            .any(|part| {
+                part.as_os_str()
+                    .to_str()
+                    .is_some_and(|p| p.starts_with('.'))
+            });
+        if hidden && !self.options.hidden {
+            return false;
+        }
+        if overrides.is_some_and(|overrides| overrides.matched(path, false).is_ignore()) {
+            return false;
+        }
+        !self.ignored(path)
+    }
+
+    /// Ignore files the walk reads, in decreasing precedence within a directory.
+    fn ignore_files(&self) -> Vec<&'static str> {
+        let mut names = Vec::new();
+        if self.options.respect_todoignore {
+            names.push(TODOIGNORE_FILE);
+        }
+        names.push(".ignore");
+        if self.options.respect_gitignore {
+            names.push(".gitignore");
+        }
+        names
+    }
+
+    /// Whether the nearest ignore file with a matching pattern, up to the
+    /// repository root, ignores `path`.
+    fn ignored(&self, path: &Path) -> bool {
+        let names = self.ignore_files();
+        for dir in path.ancestors().skip(1) {
+            for name in &names {
+                let file = dir.join(name);
+                if !file.is_file() {
+                    continue;
+                }
+                let mut builder = GitignoreBuilder::new(dir);
+                builder.add(&file);
+                let Ok(rules) = builder.build() else {
+                    continue;
+                };
+                match rules.matched_path_or_any_parents(path, false) {
+                    Match::Ignore(_) => return true,
+                    Match::Whitelist(_) => return false,
+                    Match::None => {}
+                }
+            }
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+        false
+    }
+}
+
+/// Include and exclude globs of `options` as overrides rooted at `root`.
+fn overrides(root: &Path, options: &ScanOptions) -> Result<Option<Override>> {
+    if options.include.is_empty() && options.exclude.is_empty() {
+        return Ok(None);
+    }
+
+    let mut override_builder = OverrideBuilder::new(root);
+    for pattern in &options.include {
+        override_builder
+            .add(pattern)
+            .with_context(|| format!("Invalid include pattern: {}", pattern))?;
+    }
+
+    for pattern in &options.exclude {
+        let exclude_pattern = format!("!{}", pattern);
+        override_builder
+            .add(&exclude_pattern)
+            .with_context(|| format!("Invalid exclude pattern: {}", pattern))?;
+    }
+
+    Ok(Some(override_builder.build()?))
+}
+
+/// Walker over `root` honoring the ignore files, globs and limits in `options`.
+fn walk_builder(root: &Path, options: &ScanOptions) -> Result<WalkBuilder> {
+    let mut builder = WalkBuilder::new(root);
+
+    builder
+        .hidden(!options.hidden)
+        .follow_links(options.follow_links)
+        .git_ignore(options.respect_gitignore)
+        .git_global(options.respect_gitignore)
+        .git_exclude(options.respect_gitignore);
+
+    if options.respect_todoignore {
+        builder.add_custom_ignore_filename(TODOIGNORE_FILE);
+    }
+
+    if options.max_depth > 0 {
+        builder.max_depth(Some(options.max_depth));
+    }
+
+    if options.threads > 0 {
+        builder.threads(options.threads);
+    }
+
+    if let Some(overrides) = overrides(root, options)? {
+        builder.overrides(overrides);
+    }
+
+    Ok(builder)
+}
+
+/// [`RULES_FILE`]s found during a walk, each read once and shared between threads.
+struct SubtreeRules {
+    root: PathBuf,
+    loaded: Mutex<HashMap<PathBuf, Option<Override>>>,
+}
+
+impl SubtreeRules {
+    fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            loaded: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether `path` survives the rules files above it. The nearest file
+    /// with a matching glob decides, so a subdirectory can override its parent.
+    fn allows(&self, path: &Path, is_dir: bool) -> Result<bool> {
+        for dir in path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(&self.root))
+        {
+            let Some(rules) = self.load(dir)? else {
+                continue;
+            };
+            match rules.matched(path, is_dir) {
+                Match::Ignore(_) => return Ok(false),
+                Match::Whitelist(_) => return Ok(true),
+                Match::None => {}
+            }
+        }
+        Ok(true)
+    }
+
+    /// Apply [`SubtreeRules::allows`] to a walked entry. `Some(state)` means
+    /// the walk callback should return it without visiting the entry; a
+    /// failure to read a rules file is stored in `error` and ends the walk.
+    fn check(&self, entry: &DirEntry, error: &Mutex<Option<anyhow::Error>>) -> Option<WalkState> {
+        let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+        match self.allows(entry.path(), is_dir) {
+            Ok(true) => None,
+            Ok(false) if is_dir => Some(WalkState::Skip),
+            Ok(false) => Some(WalkState::Continue),
+            Err(err) => {
+                *error.lock().unwrap() = Some(err);
+                Some(WalkState::Quit)
+            }
+        }
+    }
+
+    fn load(&self, dir: &Path) -> Result<Option<Override>> {
+        let mut loaded = self.loaded.lock().unwrap();
+        if let Some(rules) = loaded.get(dir) {
+            return Ok(rules.clone());
+        }
+
+        let path = dir.join(RULES_FILE);
+        let rules = match std::fs::read_to_string(&path) {
+            Ok(text) => Some(parse_rules(dir, &path, &text)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => {
+                return Err(err).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        };
+        loaded.insert(dir.to_path_buf(), rules.clone());
+        Ok(rules)
+    }
+}
+
+/// Parse a [`RULES_FILE`] into overrides rooted at `dir`, with the same
+/// semantics as `--include` and `--exclude`. Blank lines and `#` comments
+/// are skipped.
+fn parse_rules(dir: &Path, path: &Path, text: &str) -> Result<Override> {
+    let mut builder = OverrideBuilder::new(dir);
+    for (idx, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let glob = match line.split_once(char::is_whitespace) {
+            Some(("include", glob)) => glob.trim().to_string(),
+            Some(("exclude", glob)) => format!("!{}", glob.trim()),
+            _ => bail!(
+                "{}:{}: expected `include <glob>` or `exclude <glob>`",
+                path.display(),
+                idx + 1
+            ),
+        };
+        builder
+            .add(&glob)
+            .with_context(|| format!("{}:{}: invalid glob", path.display(), idx + 1))?;
+    }
+    Ok(builder.build()?)
+}
+
+/// Canonical form of `path`, or with `keep_link_paths` just its absolute form
+/// so symlinks stay as given. Fails if `path` does not exist either way.
+fn resolve(path: &Path, keep_link_paths: bool) -> Result<PathBuf> {
+    let canonical = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve path: {}", path.display()))?;
+    if !keep_link_paths {
+        return Ok(canonical);
+    }
+
+    std::path::absolute(path).with_context(|| format!("Failed to resolve path: {}", path.display()))
+}
+
+/// With `follow_links`, a directory reachable through several symlinks is
+/// walked under each name. Keep one name per file: the one without a link in
+/// it, else the smallest, so the choice never depends on which thread got
+/// there first. Symlinks to single files keep their own entries.
+fn one_path_per_file<T>(parsed: Vec<(PathBuf, T)>) -> Vec<(PathBuf, T)> {
+    let mut kept: HashMap<PathBuf, (bool, PathBuf, T)> = HashMap::new();
+    for (path, value) in parsed {
+        let real = path
+            .parent()
+            .and_then(|dir| dir.canonicalize().ok())
+            .zip(path.file_name())
+            .map_or_else(|| path.clone(), |(dir, name)| dir.join(name));
+        let linked = path != real;
+        match kept.get(&real) {
+            Some((kept_linked, kept_path, _)) if (*kept_linked, kept_path) <= (linked, &path) => {}
+            _ => {
+                kept.insert(real, (linked, path, value));
+            }
+        }
+    }
+    kept.into_values()
+        .map(|(_, path, value)| (path, value))
+        .collect()
+}
+
+fn is_permission_denied(err: &ignore::Error) -> bool {
+    err.io_error()
+        .is_some_and(|io_err| io_err.kind() == io::ErrorKind::PermissionDenied)
+}
+
+fn is_symlink_loop(err: &ignore::Error) -> bool {
+    match err {
+        ignore::Error::Loop { .. } => true,
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => is_symlink_loop(err),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn temp_tree(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("todo_scanner_{name}_{unique}"));
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        root
+    }
+
+    fn parser() -> TodoParser {
+        TodoParser::new(&["TODO".to_string()], true)
+    }
+
+    fn file_names(result: &ScanResult) -> Vec<String> {
+        let mut names: Vec<String> = result
+            .files_map
+            .keys()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn extension_allowlist_limits_which_files_are_read() {
+        let root = temp_tree(
+            "extensions",
+            &[
+                ("main.rs", "// TODO: rust"),
+                ("script.py", "# TODO: python"),
+                ("notes.txt", "# TODO: text"),
+                ("Makefile", "# TODO: make"),
+            ],
+        );
+
+        let options = ScanOptions {
+            extensions: vec!["rs".to_string(), ".PY".to_string()],
+            ..Default::default()
+        };
+        let result = Scanner::new(parser(), options).scan(&root).unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(file_names(&result), vec!["main.rs", "script.py"]);
+        assert_eq!(result.summary.files_scanned, 2);
+    }
+
+    #[test]
+    fn parallel_scan_matches_single_threaded_scan() {
+        let files: Vec<(String, String)> = (0..40)
+            .map(|i| {
+                let content = (0..i % 5)
+                    .map(|n| format!("// TODO: item {n}\nlet x = {n};\n"))
+                    .collect::<String>();
+                (format!("dir{}/file{i}.rs", i % 4), content)
+            })
+            .collect();
+        let files: Vec<(&str, &str)> = files
+            .iter()
+            .map(|(p, c)| (p.as_str(), c.as_str()))
+            .collect();
+        let root = temp_tree("parallel", &files);
+
+        let scan = |threads| {
+            let options = ScanOptions {
+                threads,
+                ..Default::default()
+            };
+            Scanner::new(parser(), options).scan(&root).unwrap()
+        };
+        let single = scan(1);
+        let multi = scan(4);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(single.summary, multi.summary);
+        assert_eq!(single.summary.files_scanned, 40);
+        assert_eq!(single.summary.total_count, 80);
+        assert_eq!(
+            single.sorted_items(Default::default()),
+            multi.sorted_items(Default::default())
+        );
+    }
+
+    #[test]
+    fn scan_many_combines_sibling_directories_and_files() {
+        let root = temp_tree(
+            "many",
+            &[
+                ("src/lib.rs", "// TODO: in src"),
+                ("tests/it.rs", "// TODO: in tests"),
+                ("docs/guide.md", "<!-- TODO: not requested -->"),
+                ("README.md", "<!-- TODO: in readme -->"),
+            ],
+        );
+
+        let paths = [root.join("src"), root.join("tests"), root.join("README.md")];
+        let result = Scanner::new(parser(), ScanOptions::default())
+            .scan_many(&paths)
+            .unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(file_names(&result), vec!["README.md", "it.rs", "lib.rs"]);
+        assert_eq!(result.summary.total_count, 3);
+        assert_eq!(result.summary.files_scanned, 3);
+        assert_eq!(
+            result.root,
+            Some(std::env::current_dir().unwrap().canonicalize().unwrap())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_file_is_reported_under_the_link_path() {
+        let root = temp_tree("symlink", &[("target/real.rs", "// TODO: via link")]);
+        let link = root.join("link.rs");
+        std::os::unix::fs::symlink(root.join("target/real.rs"), &link).unwrap();
+
+        let scan = |keep_link_paths| {
+            let options = ScanOptions {
+                follow_links: true,
+                keep_link_paths,
+                ..Default::default()
+            };
+            Scanner::new(parser(), options)
+                .scan_many(std::slice::from_ref(&link))
+                .unwrap()
+        };
+        let kept = scan(true);
+        let resolved = scan(false);
+        let _ = fs::remove_dir_all(&root);
+
+        let kept_paths: Vec<&PathBuf> = kept.files_map.keys().collect();
+        assert_eq!(kept_paths, [&link]);
+        assert_eq!(kept.summary.total_count, 1);
+        let resolved_paths: Vec<&PathBuf> = resolved.files_map.keys().collect();
+        assert!(resolved_paths[0].ends_with("target/real.rs"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_directories_are_walked_once() {
+        let root = temp_tree(
+            "symlink_cycle",
+            &[("a.rs", "// TODO: top"), ("sub/b.rs", "// TODO: nested")],
+        );
+        std::os::unix::fs::symlink(&root, root.join("sub/back")).unwrap();
+        std::os::unix::fs::symlink(root.join("sub"), root.join("alias")).unwrap();
+
+        let options = ScanOptions {
+            follow_links: true,
+            ..Default::default()
+        };
+        let result = Scanner::new(parser(), options).scan(&root).unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(result.summary.files_scanned, 2);
+        assert_eq!(result.summary.total_count, 2);
+        assert!(result.files_map.contains_key(&root.join("sub/b.rs")));
+        assert!(!result.files_map.contains_key(&root.join("alias/b.rs")));
+        assert!(!result.errors.is_empty());
+        assert!(
+            result.errors.iter().all(|e| e.contains("loop")),
+            "{:?}",
+            result.errors
+        );
+    }
+
+    #[test]
+    fn max_results_stops_the_walk_early() {
+        let files: Vec<(String, &str)> = (0..20)
+            .map(|i| (format!("f{i:02}.rs"), "// TODO: one\n// TODO: two"))
+            .collect();
+        let files: Vec<(&str, &str)> = files.iter().map(|(p, c)| (p.as_str(), *c)).collect();
+        let root = temp_tree("max_results", &files);
+
+        let options = ScanOptions {
+            threads: 1,
+            max_results: Some(3),
+            ..Default::default()
+        };
+        let result = Scanner::new(parser(), options).scan(&root).unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(result.summary.total_count, 3);
+        assert!(result.summary.files_scanned < 20);
+    }
+
+    #[test]
+    fn todoignore_excludes_paths_even_without_gitignore() {
+        let root = temp_tree(
+            "todoignore",
+            &[
+                (".todoignore", "vendor/\n*.gen.rs\n"),
+                ("src/lib.rs", "// TODO: ours"),
+                ("src/api.gen.rs", "// TODO: generated"),
+                ("vendor/dep/lib.rs", "// TODO: theirs"),
+            ],
+        );
+
+        let scan = |respect_todoignore| {
+            let options = ScanOptions {
+                respect_gitignore: false,
+                respect_todoignore,
+                ..Default::default()
+            };
+            Scanner::new(parser(), options).scan(&root).unwrap()
+        };
+        let ignored = scan(true);
+        let everything = scan(false);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(file_names(&ignored), vec!["lib.rs"]);
+        assert_eq!(ignored.summary.total_count, 1);
+        assert_eq!(everything.summary.total_count, 3);
+    }
+
+    #[test]
+    fn unchanged_files_are_served_from_the_cache() {
+        let root = temp_tree(
+            "cache",
+            &[
+                ("src/lib.rs", "// TODO: one\n// FIXME: two\n"),
+                ("src/main.rs", "fn main() {}\n"),
+                ("README.md", "<!-- NOTE: three -->\n"),
+            ],
+        );
+        let cache = root.join(crate::cache::CACHE_FILE);
+        let parser = || TodoParser::new(&["TODO".into(), "FIXME".into(), "NOTE".into()], true);
+        let options = ScanOptions {
+            hidden: true,
+            ..Default::default()
+        };
+        let scanner = Scanner::new(parser(), options.clone());
+
+        let (first, first_parsed) = scanner.scan_with_cache(&root, &cache).unwrap();
+        let (second, second_parsed) = scanner.scan_with_cache(&root, &cache).unwrap();
+        fs::write(root.join("src/main.rs"), "// TODO: new\nfn main() {}\n").unwrap();
+        let (third, third_parsed) = scanner.scan_with_cache(&root, &cache).unwrap();
+        let narrower = Scanner::new(TodoParser::new(&["TODO".into()], true), options);
+        let (_, narrower_parsed) = narrower.scan_with_cache(&root, &cache).unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(first_parsed, 3);
+        assert_eq!(second_parsed, 0);
+        assert_eq!(second.summary, first.summary);
+        assert_eq!(
+            second.sorted_items(Default::default()),
+            first.sorted_items(Default::default())
+        );
+        assert_eq!(third_parsed, 1);
+        assert_eq!(third.summary.total_count, 4);
+        assert_eq!(narrower_parsed, 3);
+    }
+
+    #[test]
+    fn rules_file_excludes_only_within_its_subtree() {
+        let root = temp_tree(
+            "rules",
+            &[
+                (
+                    "generated/.todo-tree",
+                    "# stubs are rewritten\nexclude *.stub.rs\n",
+                ),
+                ("generated/api.stub.rs", "// TODO: generated"),
+                ("generated/keep.rs", "// TODO: hand-written"),
+                ("src/api.stub.rs", "// TODO: outside the subtree"),
+                ("docs/.todo-tree", "include *.md\n"),
+                ("docs/guide.md", "<!-- TODO: write -->"),
+                ("docs/build.rs", "// TODO: not markdown"),
+            ],
+        );
+
+        let result = Scanner::new(parser(), ScanOptions::default())
+            .scan(&root)
+            .unwrap();
+        fs::write(root.join("src/.todo-tree"), "skip everything\n").unwrap();
+        let invalid = Scanner::new(parser(), ScanOptions::default()).scan(&root);
+        let _ = fs::remove_dir_all(&root);
+
+        let mut paths: Vec<String> = result
+            .files_map
+            .keys()
+            .map(|p| p.strip_prefix(&root).unwrap().display().to_string())
+            .collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec!["docs/guide.md", "generated/keep.rs", "src/api.stub.rs"]
+        );
+        let err = format!("{:#}", invalid.unwrap_err());
+        assert!(err.contains(".todo-tree:1"), "{}", err);
+    }
+
+    #[test]
+    fn scan_filter_applies_the_walk_rules_to_changed_paths() {
+        let root = temp_tree(
+            "filter",
+            &[
+                (".gitignore", "target/\n"),
+                (".todoignore", "vendor/\n"),
+                ("src/lib.rs", ""),
+            ],
+        );
+        fs::create_dir(root.join(".git")).unwrap();
+        let root = root.canonicalize().unwrap();
+        let options = ScanOptionsBuilder::new().exclude(["*.txt"]).build();
+        let filter = ScanFilter::new(std::slice::from_ref(&root), &options).unwrap();
+        let hidden = ScanFilter::new(
+            std::slice::from_ref(&root),
+            &ScanOptionsBuilder::new().hidden(true).build(),
+        )
+        .unwrap();
+        let matches = |path: &str| filter.matches(&root.join(path));
+        let results = [
+            matches("src/lib.rs"),
+            matches("src/new.rs"),
+            matches("notes.txt"),
+            matches("target/debug/build.rs"),
+            matches("vendor/dep.rs"),
+            matches(".cache/x.rs"),
+            matches(".gitignore"),
+            matches("src/.todo-tree"),
+            hidden.matches(&root.join(".cache/x.rs")),
+            filter.matches(Path::new("/elsewhere/lib.rs")),
+        ];
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(
+            results,
+            [
+                true, true, false, false, false, false, true, true, true, false
+            ]
+        );
+    }
+
+    #[test]
+    fn list_files_reports_included_files_without_parsing() {
+        let root = temp_tree(
+            "list",
+            &[
+                ("src/main.rs", "fn main() {}"),
+                ("src/lib.rs", "// TODO: listed"),
+                ("target/debug/build.rs", "// TODO: excluded by glob"),
+                ("generated/.todo-tree", "exclude *.rs\n"),
+                ("generated/api.rs", "// TODO: excluded by rules"),
+            ],
+        );
+
+        let options = ScanOptionsBuilder::new().exclude(["target/**"]).build();
+        let files = Scanner::new(parser(), options)
+            .list_files(std::slice::from_ref(&root))
+            .unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        let files: Vec<String> = files
+            .iter()
+            .map(|p| p.strip_prefix(&root).unwrap().display().to_string())
+            .collect();
+        assert_eq!(files, vec!["src/lib.rs", "src/main.rs"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unreadable_directories_are_recorded_when_asked() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = temp_tree(
+            "unreadable",
+            &[
+                ("lib.rs", "// TODO: seen"),
+                ("locked/hidden.rs", "// TODO: unseen"),
+            ],
+        );
+        let locked = root.join("locked");
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        // Permission bits do not stop root, so there is nothing to observe.
+        if fs::read_dir(&locked).is_ok() {
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+            let _ = fs::remove_dir_all(&root);
+            return;
+        }
+
+        let scan = |warn_unreadable| {
+            let options = ScanOptions {
+                warn_unreadable,
+                ..Default::default()
+            };
+            Scanner::new(parser(), options).scan(&root).unwrap()
+        };
+        let warned = scan(true);
+        let quiet = scan(false);
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(warned.summary.total_count, 1);
+        assert_eq!(warned.errors.len(), 1);
+        assert!(warned.errors[0].contains("locked"));
+        assert!(quiet.errors.is_empty());
+    }
+
+    #[test]
+    fn author_filter_keeps_only_that_assignee() {
+        let root = temp_tree(
+            "author",
+            &[(
+                "lib.rs",
+                "// TODO(alice): hers\n// TODO(bob): his\n// TODO: nobody's",
+            )],
+        );
+
+        let result = Scanner::new(parser(), ScanOptions::default())
+            .scan(&root)
+            .unwrap();
+        let _ = fs::remove_dir_all(&root);
+        let filtered = result.filter_by_author("ALICE");
+
+        let items = filtered.all_items();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].1.message, "hers");
+        assert_eq!(filtered.summary.total_count, 1);
+        assert_eq!(filtered.summary.files_with_todos, 1);
+    }
+}
@@ -0,0 +1,179 @@
+use anyhow::Result;
+use std::fmt;
+use std::str::FromStr;
+use todo_tree_core::ScanSummary;
+
+/// A `TAG=N` limit from `--max-tag`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagLimit {
+    pub tag: String,
+    pub max: usize,
+}
+
+impl FromStr for TagLimit {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (tag, max) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected TAG=N, got `{}`", s))?;
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return Err(format!("missing tag name in `{}`", s));
+        }
+        let max = max
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid count in `{}`", s))?;
+
+        Ok(Self {
+            tag: tag.to_string(),
+            max,
+        })
+    }
+}
+
+/// A failed threshold; the process exits with
+/// [`crate::exit_codes::CHECK_FAILED`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThresholdError {
+    pub message: String,
+}
+
+impl fmt::Display for ThresholdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ThresholdError {}
+
+/// Count limits checked after a scan; exceeding any of them fails the run.
+#[derive(Debug, Clone, Default)]
+pub struct Thresholds {
+    pub max_total: Option<usize>,
+    pub max_tags: Vec<TagLimit>,
+    /// Fail if there is any item at all.
+    pub fail_on_found: bool,
+}
+
+impl Thresholds {
+    /// Compare `summary` against every limit and fail with a [`ThresholdError`]
+    /// listing all that were exceeded.
+    pub fn check(&self, summary: &ScanSummary) -> Result<()> {
+        if self.fail_on_found && !summary.is_empty() {
+            return Err(ThresholdError {
+                message: format!("Found {} TODO items", summary.total_count),
+            }
+            .into());
+        }
+
+        let mut exceeded = Vec::new();
+
+        if let Some(max) = self.max_total
+            && summary.total_count > max
+        {
+            exceeded.push(format!("{} total (max {})", summary.total_count, max));
+        }
+
+        for limit in &self.max_tags {
+            let count: usize = summary
+                .tag_counts
+                .iter()
+                .filter(|(tag, _)| tag.eq_ignore_ascii_case(&limit.tag))
+                .map(|(_, count)| count)
+                .sum();
+            if count > limit.max {
+                exceeded.push(format!("{} {} (max {})", count, limit.tag, limit.max));
+            }
+        }
+
+        if !exceeded.is_empty() {
+            return Err(ThresholdError {
+                message: format!("Threshold exceeded: {}", exceeded.join(", ")),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use todo_tree_core::{ScanResult, TodoItem};
+
+    fn summary(tags: &[&str]) -> ScanSummary {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        let items = tags
+            .iter()
+            .enumerate()
+            .map(|(i, tag)| TodoItem {
+                tag: tag.to_string(),
+                line: i + 1,
+                ..Default::default()
+            })
+            .collect();
+        result.add_file(PathBuf::from("/repo/main.rs"), items);
+        result.summary
+    }
+
+    #[test]
+    fn parses_tag_limits() {
+        assert_eq!(
+            "BUG=0".parse::<TagLimit>(),
+            Ok(TagLimit {
+                tag: "BUG".to_string(),
+                max: 0
+            })
+        );
+        assert!("BUG".parse::<TagLimit>().is_err());
+        assert!("=3".parse::<TagLimit>().is_err());
+        assert!("BUG=lots".parse::<TagLimit>().is_err());
+    }
+
+    #[test]
+    fn any_bug_fails_max_tag_zero() {
+        let thresholds = Thresholds {
+            max_tags: vec!["BUG=0".parse().unwrap()],
+            ..Default::default()
+        };
+
+        assert!(thresholds.check(&summary(&["TODO", "TODO"])).is_ok());
+
+        let err = thresholds
+            .check(&summary(&["TODO", "BUG"]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("1 BUG (max 0)"), "{}", err);
+    }
+
+    #[test]
+    fn fail_on_found_fails_on_any_item() {
+        let thresholds = Thresholds {
+            fail_on_found: true,
+            ..Default::default()
+        };
+
+        assert!(thresholds.check(&summary(&[])).is_ok());
+        let err = thresholds.check(&summary(&["NOTE"])).unwrap_err();
+        assert!(err.downcast_ref::<ThresholdError>().is_some());
+    }
+
+    #[test]
+    fn max_total_allows_counts_up_to_the_limit() {
+        let thresholds = Thresholds {
+            max_total: Some(2),
+            ..Default::default()
+        };
+
+        assert!(thresholds.check(&summary(&["TODO", "FIXME"])).is_ok());
+        assert!(
+            thresholds
+                .check(&summary(&["TODO", "FIXME", "NOTE"]))
+                .is_err()
+        );
+    }
+}
@@ -9,3 +9,16 @@ pub fn priority_to_color(priority: Priority) -> Color {
         Priority::Low => Color::Green,
     }
 }
+
+/// `count` with `,` between groups of three digits, e.g. `1,234,567`.
+pub fn group_thousands(count: usize) -> String {
+    let digits = count.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (idx, digit) in digits.chars().enumerate() {
+        if idx > 0 && (digits.len() - idx).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+
+/// Render the time between `then` and `now` as e.g. "3 months ago".
+///
+/// Months are 30 days and years 365, which is close enough for triage.
+pub fn humanize_since(then: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - then).num_seconds();
+    if seconds < 60 {
+        return "just now".to_string();
+    }
+
+    let (count, unit) = match seconds {
+        s if s < 3_600 => (s / 60, "minute"),
+        s if s < 86_400 => (s / 3_600, "hour"),
+        s if s < 30 * 86_400 => (s / 86_400, "day"),
+        s if s < 365 * 86_400 => (s / (30 * 86_400), "month"),
+        s => (s / (365 * 86_400), "year"),
+    };
+
+    if count == 1 {
+        format!("1 {} ago", unit)
+    } else {
+        format!("{} {}s ago", count, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn humanizes_common_spans() {
+        let now = Utc::now();
+
+        assert_eq!(
+            humanize_since(now - Duration::days(90), now),
+            "3 months ago"
+        );
+        assert_eq!(
+            humanize_since(now - Duration::days(92), now),
+            "3 months ago"
+        );
+        assert_eq!(humanize_since(now - Duration::hours(1), now), "1 hour ago");
+        assert_eq!(
+            humanize_since(now - Duration::days(800), now),
+            "2 years ago"
+        );
+        assert_eq!(humanize_since(now - Duration::seconds(5), now), "just now");
+        assert_eq!(humanize_since(now + Duration::days(1), now), "just now");
+    }
+}
@@ -0,0 +1,203 @@
+//! Rescanning when files change, driven by file system notifications.
+//! Editors that save atomically write a temporary file and rename it over the
+//! original; that arrives as a rename, or a create and remove pair, naming
+//! the original path, and counts as a change like any write.
+
+use anyhow::{Context, Result};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+/// How long changes must stop before a rescan, so a burst of saves (a branch
+/// switch, a formatter run) reprints once.
+pub const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// ANSI sequence clearing the terminal and moving the cursor home.
+pub const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Events from a watcher, or the errors it ran into.
+pub type Events = Receiver<notify::Result<Event>>;
+
+/// Watch `paths`, directories recursively. Events arrive on the returned
+/// channel until the watcher is dropped.
+pub fn watch<'a>(
+    paths: impl IntoIterator<Item = &'a Path>,
+) -> Result<(RecommendedWatcher, Events)> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).context("Failed to start watching")?;
+    for path in paths {
+        let mode = if path.is_dir() {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher
+            .watch(path, mode)
+            .with_context(|| format!("Failed to watch {}", path.display()))?;
+    }
+    Ok((watcher, receiver))
+}
+
+/// Whether `event` may change what a scan finds: a file created, written,
+/// renamed or removed at a path `relevant` accepts. Reads and metadata-only
+/// changes never count; a watcher error or overflow always does, since
+/// changes may have been missed.
+pub fn is_change(event: &notify::Result<Event>, relevant: impl Fn(&Path) -> bool) -> bool {
+    let Ok(event) = event else {
+        return true;
+    };
+    if event.need_rescan() {
+        return true;
+    }
+    match event.kind {
+        EventKind::Create(_)
+        | EventKind::Remove(_)
+        | EventKind::Modify(ModifyKind::Name(_) | ModifyKind::Data(_) | ModifyKind::Any)
+        | EventKind::Any => event.paths.iter().any(|path| relevant(path)),
+        EventKind::Access(_) | EventKind::Modify(_) | EventKind::Other => false,
+    }
+}
+
+/// Call `rescan` once per burst of events, starting with one that
+/// [`is_change`] accepts and ending when none has arrived for `debounce`.
+/// Returns when the watcher hangs up or `rescan` fails.
+pub fn run_debounced<R, F>(
+    events: &Events,
+    debounce: Duration,
+    relevant: R,
+    mut rescan: F,
+) -> Result<()>
+where
+    R: Fn(&Path) -> bool,
+    F: FnMut() -> Result<()>,
+{
+    while let Ok(event) = events.recv() {
+        if !is_change(&event, &relevant) {
+            continue;
+        }
+        while events.recv_timeout(debounce).is_ok() {}
+        rescan()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, DataChange, MetadataKind, RemoveKind, RenameMode};
+    use std::fs;
+    use std::path::PathBuf;
+    use std::thread;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn event(kind: EventKind, paths: &[&str]) -> notify::Result<Event> {
+        Ok(paths.iter().fold(Event::new(kind), |event, path| {
+            event.add_path(PathBuf::from(path))
+        }))
+    }
+
+    fn rust_files(path: &Path) -> bool {
+        path.extension().is_some_and(|ext| ext == "rs")
+    }
+
+    #[test]
+    fn a_burst_of_changes_rescans_once() {
+        let (sender, receiver) = mpsc::channel();
+        let write = || {
+            event(
+                EventKind::Modify(ModifyKind::Data(DataChange::Content)),
+                &["/r/lib.rs"],
+            )
+        };
+        let feeder = thread::spawn(move || {
+            for _ in 0..3 {
+                sender.send(write()).unwrap();
+            }
+            thread::sleep(Duration::from_millis(100));
+            sender
+                .send(event(EventKind::Access(AccessKind::Any), &["/r/lib.rs"]))
+                .unwrap();
+            sender
+                .send(event(
+                    EventKind::Create(CreateKind::File),
+                    &["/r/notes.txt"],
+                ))
+                .unwrap();
+            thread::sleep(Duration::from_millis(100));
+            sender.send(write()).unwrap();
+        });
+
+        let mut rescans = 0;
+        run_debounced(&receiver, Duration::from_millis(40), rust_files, || {
+            rescans += 1;
+            Ok(())
+        })
+        .unwrap();
+        feeder.join().unwrap();
+
+        assert_eq!(rescans, 2);
+    }
+
+    #[test]
+    fn atomic_saves_count_as_changes() {
+        let rename = |mode| EventKind::Modify(ModifyKind::Name(mode));
+
+        assert!(is_change(
+            &event(rename(RenameMode::Both), &["/r/.lib.rs.swp", "/r/lib.rs"]),
+            rust_files
+        ));
+        assert!(is_change(
+            &event(rename(RenameMode::To), &["/r/lib.rs"]),
+            rust_files
+        ));
+        assert!(is_change(
+            &event(EventKind::Remove(RemoveKind::File), &["/r/lib.rs"]),
+            rust_files
+        ));
+        assert!(is_change(
+            &event(EventKind::Create(CreateKind::File), &["/r/lib.rs"]),
+            rust_files
+        ));
+        assert!(!is_change(
+            &event(rename(RenameMode::From), &["/r/lib.rs.tmp"]),
+            rust_files
+        ));
+        assert!(!is_change(
+            &event(
+                EventKind::Modify(ModifyKind::Metadata(MetadataKind::Permissions)),
+                &["/r/lib.rs"]
+            ),
+            rust_files
+        ));
+        assert!(is_change(
+            &Err(notify::Error::generic("overflow")),
+            rust_files
+        ));
+    }
+
+    #[test]
+    fn writing_a_file_is_reported_by_the_watcher() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("todo_watch_write_{unique}"));
+        fs::create_dir_all(&root).unwrap();
+        let root = root.canonicalize().unwrap();
+        fs::write(root.join("lib.rs"), "// TODO: first\n").unwrap();
+
+        let (_watcher, events) = watch([root.as_path()]).unwrap();
+        fs::write(root.join("lib.rs"), "// TODO: first\n// TODO: second\n").unwrap();
+        let lib = root.join("lib.rs");
+        let changed = (0..50).any(|_| {
+            events
+                .recv_timeout(Duration::from_millis(100))
+                .is_ok_and(|event| is_change(&event, |path| path == lib))
+        });
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(changed);
+    }
+}
@@ -0,0 +1,107 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn fixture(name: &str) -> PathBuf {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("todo_exit_{name}_{unique}"));
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(
+        root.join("src/main.rs"),
+        "// TODO: first\nfn main() {}\n// TODO: second\n",
+    )
+    .unwrap();
+    root
+}
+
+/// Run `todo-tree scan` on `root`, isolated from any user-level config.
+fn scan(root: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_todo-tree"))
+        .arg("scan")
+        .arg(root)
+        .args(["--no-color", "--flat"])
+        .args(args)
+        .env("HOME", root)
+        .env("XDG_CONFIG_HOME", root)
+        .output()
+        .expect("failed to run todo-tree")
+}
+
+#[test]
+fn fail_on_found_exits_with_code_one() {
+    let root = fixture("found");
+    let found = scan(&root, &["--fail-on-found"]);
+    let critical_only = scan(&root, &["--fail-on-found", "--min-priority", "critical"]);
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert_eq!(found.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&found.stderr).contains("Found 2 TODO items"));
+    assert!(!String::from_utf8_lossy(&found.stderr).contains("panicked"));
+    assert_eq!(critical_only.status.code(), Some(0));
+}
+
+#[test]
+fn max_allowed_fails_only_above_the_budget() {
+    let root = fixture("budget");
+    let within = scan(&root, &["--max-allowed", "2"]);
+    let over = scan(&root, &["--max-allowed", "1"]);
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert_eq!(within.status.code(), Some(0));
+    assert_eq!(over.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&over.stderr).contains("Threshold exceeded"));
+}
+
+#[test]
+fn usage_and_config_errors_exit_with_code_two() {
+    let root = fixture("usage");
+    std::fs::write(
+        root.join(".todorc.json"),
+        r#"{"tags": ["TODO"], "custom_pattern": "(unclosed"}"#,
+    )
+    .unwrap();
+    let unknown_flag = scan(&root, &["--no-such-flag"]);
+    let bad_pattern = scan(&root, &[]);
+    std::fs::write(root.join(".todorc.json"), r#"{"tags": 42}"#).unwrap();
+    let bad_config = scan(&root, &[]);
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert_eq!(unknown_flag.status.code(), Some(2));
+    assert_eq!(bad_pattern.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&bad_pattern.stderr).contains("Invalid tag pattern"));
+    assert_eq!(bad_config.status.code(), Some(2));
+}
+
+#[test]
+fn io_errors_exit_with_code_three() {
+    let root = fixture("io");
+    let missing = scan(&root.join("does-not-exist"), &[]);
+    let ok = scan(&root, &[]);
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert_eq!(missing.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("Failed to resolve path"));
+    assert_eq!(ok.status.code(), Some(0));
+}
+
+#[test]
+fn help_documents_the_exit_codes() {
+    let help = Command::new(env!("CARGO_BIN_EXE_todo-tree"))
+        .arg("--help")
+        .output()
+        .expect("failed to run todo-tree");
+    let scan_help = Command::new(env!("CARGO_BIN_EXE_todo-tree"))
+        .args(["scan", "--help"])
+        .output()
+        .expect("failed to run todo-tree");
+    let help = String::from_utf8_lossy(&help.stdout);
+    let scan_help = String::from_utf8_lossy(&scan_help.stdout);
+
+    assert!(help.contains("Exit codes:"), "{}", help);
+    assert!(help.contains("1  A check failed"), "{}", help);
+    assert!(scan_help.contains("Exit with code 1 if any item is found"));
+    assert!(!scan_help.contains("code 2"), "{}", scan_help);
+}
@@ -0,0 +1,58 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn fixture(name: &str) -> PathBuf {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("todo_ids_{name}_{unique}"));
+    for dir in ["src", "tests"] {
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+        std::fs::write(root.join(dir).join("lib.rs"), "// TODO: same words\n").unwrap();
+    }
+    root
+}
+
+/// Run `todo-tree scan` from inside `root`, isolated from any user-level config.
+fn scan_from(root: &Path, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_todo-tree"))
+        .arg("scan")
+        .args(args)
+        .current_dir(root)
+        .env("HOME", root)
+        .env("XDG_CONFIG_HOME", root)
+        .output()
+        .expect("failed to run todo-tree");
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn ids(json: &str) -> BTreeSet<String> {
+    let report: serde_json::Value = serde_json::from_str(json).unwrap();
+    report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|file| file["items"].as_array().unwrap())
+        .map(|item| item["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn same_named_files_in_sibling_directories_get_distinct_ids() {
+    let root = fixture("siblings");
+    let files = ids(&scan_from(&root, &["src/lib.rs", "tests/lib.rs", "--json"]));
+    let tree = ids(&scan_from(&root, &[".", "--json"]));
+    let one = ids(&scan_from(&root, &["src/lib.rs", "--json"]));
+    let files_fingerprint = scan_from(&root, &["src/lib.rs", "tests/lib.rs", "--fingerprint"]);
+    let tree_fingerprint = scan_from(&root, &[".", "--fingerprint"]);
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert_eq!(files.len(), 2);
+    assert_eq!(files, tree);
+    assert!(one.is_subset(&tree));
+    assert_eq!(files_fingerprint, tree_fingerprint);
+}
@@ -0,0 +1,156 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn fixture(name: &str) -> PathBuf {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("todo_output_{name}_{unique}"));
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(
+        root.join("src/main.rs"),
+        "// TODO: first\nfn main() {}\n// FIXME: second\n",
+    )
+    .unwrap();
+    root
+}
+
+/// Run `todo-tree scan` on `root`, isolated from any user-level config.
+fn scan(root: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_todo-tree"))
+        .arg("scan")
+        .arg(root)
+        .args(args)
+        .env("HOME", root)
+        .env("XDG_CONFIG_HOME", root)
+        .output()
+        .expect("failed to run todo-tree")
+}
+
+#[test]
+fn output_file_takes_its_format_from_the_extension() {
+    let root = fixture("extension");
+    let json = root.join("reports/nested/todos.json");
+    let html = root.join("reports/todos.html");
+    let as_json = scan(&root, &["-o", json.to_str().unwrap()]);
+    let as_html = scan(&root, &["--output", html.to_str().unwrap()]);
+    let json_text = std::fs::read_to_string(&json).unwrap();
+    let html_text = std::fs::read_to_string(&html).unwrap();
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert!(as_json.status.success());
+    assert!(as_json.stdout.is_empty());
+    let report: serde_json::Value = serde_json::from_str(&json_text).unwrap();
+    assert_eq!(report["summary"]["total_count"], 2);
+    assert_eq!(report["files"][0]["items"][1]["message"], "second");
+
+    assert!(as_html.status.success());
+    assert!(html_text.contains("<table"), "{}", html_text);
+}
+
+#[test]
+fn explicit_format_wins_over_the_extension() {
+    let root = fixture("explicit");
+    let out = root.join("todos.json");
+    let run = scan(&root, &["--format", "minimal", "-o", out.to_str().unwrap()]);
+    let text = std::fs::read_to_string(&out).unwrap();
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert!(run.status.success());
+    assert_eq!(
+        text,
+        "src/main.rs:1: TODO first\nsrc/main.rs:3: FIXME second\n"
+    );
+}
+
+#[test]
+fn format_flags_win_over_a_config_github_default() {
+    let root = fixture("config_github");
+    std::fs::write(
+        root.join(".todorc.json"),
+        r#"{"root": true, "github": true}"#,
+    )
+    .unwrap();
+    let json = scan(&root, &["--json"]);
+    let csv = scan(&root, &["--csv"]);
+    let default = scan(&root, &[]);
+    let _ = std::fs::remove_dir_all(&root);
+
+    let report: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+    assert_eq!(report["summary"]["total_count"], 2);
+    let csv = String::from_utf8(csv.stdout).unwrap();
+    assert!(csv.starts_with("file,line,column,tag"), "{}", csv);
+    let default = String::from_utf8(default.stdout).unwrap();
+    assert!(default.starts_with("::warning "), "{}", default);
+}
+
+#[test]
+fn codequality_flag_wins_over_the_extension() {
+    let root = fixture("codequality");
+    let out = root.join("gl-code-quality-report.json");
+    let run = scan(&root, &["--codequality", "-o", out.to_str().unwrap()]);
+    let text = std::fs::read_to_string(&out).unwrap();
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert!(run.status.success());
+    let report: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(report.as_array().unwrap().len(), 2);
+    assert_eq!(report[0]["check_name"], "TODO");
+    assert_eq!(report[0]["location"]["path"], "src/main.rs");
+}
+
+#[test]
+fn junit_flag_writes_one_failure_per_item() {
+    let root = fixture("junit");
+    let out = root.join("todo-report.txt");
+    let run = scan(&root, &["--junit", "-o", out.to_str().unwrap()]);
+    let text = std::fs::read_to_string(&out).unwrap();
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert!(run.status.success());
+    assert!(text.starts_with("<?xml"), "{}", text);
+    assert!(text.contains(r#"<testcase classname="todo-tree" name="src/main.rs""#));
+    assert_eq!(text.matches("<failure ").count(), 2);
+}
+
+#[test]
+fn fingerprint_goes_to_the_output_file() {
+    let root = fixture("fingerprint");
+    let out = root.join("fingerprint.txt");
+    let to_file = scan(&root, &["--fingerprint", "-o", out.to_str().unwrap()]);
+    let to_stdout = scan(&root, &["--fingerprint"]);
+    let text = std::fs::read_to_string(&out).unwrap();
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert!(to_file.status.success());
+    assert!(to_file.stdout.is_empty());
+    assert_eq!(text.trim().len(), 16);
+    assert_eq!(text.as_bytes(), to_stdout.stdout.as_slice());
+}
+
+#[test]
+fn dry_run_list_goes_to_the_output_file() {
+    let root = fixture("dry_run");
+    let out = root.join("files.txt");
+    let run = scan(&root, &["--dry-run", "-o", out.to_str().unwrap()]);
+    let text = std::fs::read_to_string(&out).unwrap();
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert!(run.status.success());
+    assert!(run.stdout.is_empty());
+    assert_eq!(text, "src/main.rs\n");
+}
+
+#[test]
+fn unwritable_output_is_an_io_error() {
+    let root = fixture("unwritable");
+    std::fs::write(root.join("blocker"), "").unwrap();
+    let out = root.join("blocker/todos.json");
+    let run = scan(&root, &["-o", out.to_str().unwrap()]);
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert_eq!(run.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&run.stderr).contains("Failed to create directory"));
+}
@@ -1,9 +1,10 @@
+pub mod hash;
 pub mod parser;
 pub mod priority;
 pub mod tags;
 pub mod types;
 
 pub use parser::DEFAULT_REGEX;
-pub use priority::Priority;
-pub use tags::{DEFAULT_TAGS, TagDefinition};
-pub use types::{FileResult, ScanResult, ScanSummary, TodoItem};
+pub use priority::{ParsePriorityError, Priority};
+pub use tags::{CustomTag, DEFAULT_TAGS, TagDefinition, TagRegistry};
+pub use types::{FileResult, ItemLocation, ScanResult, ScanSummary, SortKey, TodoItem};
@@ -6,10 +6,13 @@
 /// Pattern breakdown:
 /// - `(//|#|<!--|;|/\*|\*|--)`  - Comment markers for most languages
 /// - `\s*`                       - Optional whitespace after comment marker
-/// - `($TAGS)`                   - The tag to match (placeholder, replaced at runtime)
-/// - `(?:\(([^)]+)\))?`          - Optional author in parentheses
+/// - `(?P<tag>$TAGS)`            - The tag to match (placeholder, replaced at runtime)
+/// - `(?:\((?P<author>[^)]+)\))?` - Optional author in parentheses
 /// - `:`                         - Required colon after tag
-/// - `(.*)`                      - The message
+/// - `(?P<message>.*)`           - The message
+///
+/// Custom patterns must expose the same `tag` capture group, and may expose
+/// `author` and `message`.
 ///
 /// Supported comment syntaxes:
 /// ```text
@@ -29,5 +32,4 @@
 /// Note: `::` was removed from default comment markers to prevent false positives
 /// in Rust, C++, and other languages where `::` is used as a scope resolution operator
 /// (e.g., `std::io::Error`).
-pub const DEFAULT_REGEX: &str =
-    r#"(//|#|<!--|;|/\*|\*|--|%|"""|'''|REM\s)\s*($TAGS)(?:\(([^)]+)\))?:(.*)"#;
+pub const DEFAULT_REGEX: &str = r#"(//|#|<!--|;|/\*|\*|--|%|"""|'''|REM\s)\s*(?P<tag>$TAGS)(?:\((?P<author>[^)]+)\))?:(?P<message>.*)"#;
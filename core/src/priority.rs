@@ -1,10 +1,19 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+/// Serialized in lowercase; the capitalized names written by earlier
+/// versions are still accepted when reading.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
 pub enum Priority {
+    #[serde(alias = "Low")]
     Low,
+    #[default]
+    #[serde(alias = "Medium")]
     Medium,
+    #[serde(alias = "High")]
     High,
+    #[serde(alias = "Critical")]
     Critical,
 }
 
@@ -19,6 +28,17 @@ impl Priority {
         }
     }
 
+    /// Numeric rank for consumers that sort on a number: 0 for Low up to 3
+    /// for Critical.
+    pub fn severity(&self) -> u8 {
+        match self {
+            Priority::Low => 0,
+            Priority::Medium => 1,
+            Priority::High => 2,
+            Priority::Critical => 3,
+        }
+    }
+
     pub fn display_name(&self) -> &'static str {
         match self {
             Priority::Critical => "Critical",
@@ -34,3 +54,93 @@ impl std::fmt::Display for Priority {
         write!(f, "{}", self.display_name())
     }
 }
+
+/// Error returned when a string is neither a priority name nor a number 1–4.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePriorityError(String);
+
+impl std::fmt::Display for ParsePriorityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid priority `{}` (expected low, medium, high, critical or 1-4)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParsePriorityError {}
+
+impl std::str::FromStr for Priority {
+    type Err = ParsePriorityError;
+
+    /// Accepts names case-insensitively, or `1`–`4` for Low through Critical.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "low" | "1" => Ok(Priority::Low),
+            "medium" | "2" => Ok(Priority::Medium),
+            "high" | "3" => Ok(Priority::High),
+            "critical" | "4" => Ok(Priority::Critical),
+            _ => Err(ParsePriorityError(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_and_numeric_aliases() {
+        assert_eq!("3".parse::<Priority>(), Ok(Priority::High));
+        assert_eq!("1".parse::<Priority>(), Ok(Priority::Low));
+        assert_eq!("Critical".parse::<Priority>(), Ok(Priority::Critical));
+        assert_eq!("medium".parse::<Priority>(), Ok(Priority::Medium));
+        assert!("5".parse::<Priority>().is_err());
+        assert!("urgent".parse::<Priority>().is_err());
+    }
+
+    #[test]
+    fn every_variant_round_trips_through_display_and_parse() {
+        for priority in [
+            Priority::Low,
+            Priority::Medium,
+            Priority::High,
+            Priority::Critical,
+        ] {
+            assert_eq!(priority.to_string().parse::<Priority>(), Ok(priority));
+        }
+    }
+
+    #[test]
+    fn severity_follows_the_ordering() {
+        assert_eq!(Priority::Low.severity(), 0);
+        assert_eq!(Priority::Critical.severity(), 3);
+        assert!(Priority::High.severity() > Priority::Medium.severity());
+    }
+
+    #[test]
+    fn unknown_name_is_a_clear_error() {
+        let err = "urgent".parse::<Priority>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid priority `urgent` (expected low, medium, high, critical or 1-4)"
+        );
+    }
+
+    #[test]
+    fn serializes_lowercase_and_reads_old_capitalized_names() {
+        assert_eq!(
+            serde_json::to_string(&Priority::Critical).unwrap(),
+            "\"critical\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Priority>("\"high\"").unwrap(),
+            Priority::High
+        );
+        assert_eq!(
+            serde_json::from_str::<Priority>("\"High\"").unwrap(),
+            Priority::High
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::priority::Priority;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct TagDefinition {
@@ -108,3 +109,99 @@ pub fn find_tag(name: &str) -> Option<&'static TagDefinition> {
         .iter()
         .find(|t| t.name.eq_ignore_ascii_case(name))
 }
+
+/// Owned counterpart of [`TagDefinition`] for tags defined at runtime, e.g. in config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomTag {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub priority: Priority,
+}
+
+impl From<&TagDefinition> for CustomTag {
+    fn from(tag: &TagDefinition) -> Self {
+        Self {
+            name: tag.name.to_string(),
+            description: tag.description.to_string(),
+            priority: tag.priority,
+        }
+    }
+}
+
+/// [`DEFAULT_TAGS`] merged with user definitions. A user entry replaces the
+/// default of the same name (case-insensitively); new names are appended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagRegistry {
+    tags: Vec<CustomTag>,
+}
+
+impl Default for TagRegistry {
+    fn default() -> Self {
+        Self {
+            tags: DEFAULT_TAGS.iter().map(CustomTag::from).collect(),
+        }
+    }
+}
+
+impl TagRegistry {
+    pub fn new(custom: impl IntoIterator<Item = CustomTag>) -> Self {
+        let mut registry = Self::default();
+        for mut tag in custom {
+            tag.name = tag.name.trim().to_string();
+            if tag.name.is_empty() {
+                continue;
+            }
+            match registry
+                .tags
+                .iter_mut()
+                .find(|t| t.name.eq_ignore_ascii_case(&tag.name))
+            {
+                Some(existing) => *existing = tag,
+                None => registry.tags.push(tag),
+            }
+        }
+        registry
+    }
+
+    pub fn find(&self, name: &str) -> Option<&CustomTag> {
+        self.tags.iter().find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.tags.iter().map(|t| t.name.clone()).collect()
+    }
+
+    pub fn all(&self) -> &[CustomTag] {
+        &self.tags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_merges_user_tags_with_defaults() {
+        let registry = TagRegistry::new([
+            CustomTag {
+                name: "REVIEW".to_string(),
+                description: "Needs a second pair of eyes".to_string(),
+                priority: Priority::High,
+            },
+            CustomTag {
+                name: "todo".to_string(),
+                description: "Overridden".to_string(),
+                priority: Priority::Low,
+            },
+        ]);
+
+        let review = registry.find("review").expect("custom tag should be found");
+        assert_eq!(review.priority, Priority::High);
+        assert_eq!(registry.find("TODO").unwrap().priority, Priority::Low);
+        assert_eq!(registry.find("BUG").unwrap().priority, Priority::Critical);
+        assert_eq!(registry.all().len(), DEFAULT_TAGS.len() + 1);
+        assert_eq!(registry.names().last().map(String::as_str), Some("REVIEW"));
+    }
+}
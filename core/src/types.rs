@@ -1,30 +1,159 @@
+use crate::hash::{SplitMix64, fnv1a_64};
 use crate::priority::Priority;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct TodoItem {
     pub tag: String,
+    /// The tag as written, when case-insensitive matching folded it to a
+    /// differently-cased `tag`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched_tag: Option<String>,
     pub message: String,
     pub line: usize,
     pub column: usize,
+    /// Column with each tab counted as one character, set when `column` was
+    /// computed with tabs expanded to a tab width.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_column: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line_content: Option<String>,
+    /// First assignee, kept for compatibility with single-author consumers.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub author: Option<String>,
+    /// Every assignee listed in the parentheses, e.g. `TODO(alice, bob):`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
     pub priority: Priority,
+    /// Line range mentioned in the message, e.g. `see lines 40-52`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub referenced_lines: Option<(usize, usize)>,
+    /// First issue reference in the message, e.g. `#123` or `PROJ-42`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+    /// Deadline from `TODO(2024-12-31):` or a `due:2024-12-31` token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+    /// Author of the commit that last changed the line, with `--blame`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blame_author: Option<String>,
+    /// Time of the commit that last changed the line, with `--blame`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blame_date: Option<DateTime<Utc>>,
+    /// Continuation lines that follow the tag inside the same comment.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub body: Vec<String>,
+    /// Source lines just before the tag line, with `--context`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_before: Vec<String>,
+    /// Source lines just after the tag line, with `--context`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub context_after: Vec<String>,
+    /// Other places the same tag and message appear, after
+    /// [`ScanResult::deduplicated`] collapsed them into this item.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<ItemLocation>,
+    /// Stable id set by [`ScanResult::assign_ids`] on the full scan, so
+    /// filtering items out later never changes the ids of the rest.
+    #[serde(skip)]
+    pub id: Option<String>,
+}
+
+/// Where an item was found.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ItemLocation {
+    pub path: PathBuf,
+    pub line: usize,
 }
 
 impl TodoItem {
     pub fn format_author(&self) -> String {
+        if self.authors.len() > 1 {
+            format!("({})", self.authors.join(", "))
+        } else {
+            self.author
+                .as_ref()
+                .map(|a| format!("({})", a))
+                .unwrap_or_default()
+        }
+    }
+
+    /// Identity that survives line shifts: a hash of the file path, tag and message.
+    ///
+    /// `path` should be relative to the scan root so ids match across checkouts.
+    /// `occurrence` counts identical items above this one in the file, so each
+    /// gets its own id; the first keeps the plain path, tag and message hash.
+    pub fn stable_id(&self, path: &str, occurrence: usize) -> String {
+        let mut key = format!(
+            "{}\0{}\0{}",
+            path.replace('\\', "/"),
+            self.tag,
+            self.message
+        );
+        if occurrence > 0 {
+            key.push_str(&format!("\0{}", occurrence));
+        }
+        format!("{:016x}", fnv1a_64(key.as_bytes()))
+    }
+
+    /// Sort key for [`SortKey::Age`]: undated items after every dated one.
+    pub fn age_key(&self) -> (bool, Option<DateTime<Utc>>) {
+        (self.blame_date.is_none(), self.blame_date)
+    }
+
+    /// Number of files this item appears in: `path`, where it was kept, plus
+    /// those of its duplicates.
+    pub fn seen_in_files(&self, path: &Path) -> usize {
+        let mut files: HashSet<&Path> = self.duplicates.iter().map(|d| d.path.as_path()).collect();
+        files.insert(path);
+        files.len()
+    }
+
+    pub fn has_author(&self, author: &str) -> bool {
         self.author
-            .as_ref()
-            .map(|a| format!("({})", a))
-            .unwrap_or_default()
+            .iter()
+            .chain(&self.authors)
+            .any(|a| a.eq_ignore_ascii_case(author))
+    }
+}
+
+/// Compact, color-free `TODO(alice)[Medium] 3:4: message` line without the
+/// path. The author part is left out when there is none.
+impl std::fmt::Display for TodoItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}[{}] {}:{}:",
+            self.tag,
+            self.format_author(),
+            self.priority,
+            self.line,
+            self.column
+        )?;
+        if !self.message.is_empty() {
+            write!(f, " {}", self.message)?;
+        }
+        Ok(())
     }
 }
 
+/// Ordering for [`ScanResult::sorted_items`]. Ties fall back to path, then line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortKey {
+    #[default]
+    Path,
+    Line,
+    /// Critical first, down to Low.
+    Priority,
+    Tag,
+    /// Oldest `blame_date` first; items without one last.
+    Age,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct FileResult {
     pub path: String,
@@ -40,6 +169,22 @@ pub struct ScanSummary {
 }
 
 impl ScanSummary {
+    /// Whether no items were counted.
+    pub fn is_empty(&self) -> bool {
+        self.total_count == 0
+    }
+
+    /// Per-tag counts, most frequent first and ties by tag name.
+    pub fn tags_by_count(&self) -> Vec<(&str, usize)> {
+        let mut tags: Vec<(&str, usize)> = self
+            .tag_counts
+            .iter()
+            .map(|(tag, count)| (tag.as_str(), *count))
+            .collect();
+        tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        tags
+    }
+
     pub fn avg_items_per_file(&self) -> f64 {
         if self.files_with_todos > 0 {
             self.total_count as f64 / self.files_with_todos as f64
@@ -66,6 +211,16 @@ pub struct ScanResult {
     pub summary: ScanSummary,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub root: Option<PathBuf>,
+    /// Originating root of files brought in by [`ScanResult::merge`].
+    #[serde(skip)]
+    pub file_roots: HashMap<PathBuf, PathBuf>,
+    /// Every file passed to [`ScanResult::add_file`], with or without items.
+    #[serde(skip)]
+    pub scanned: HashSet<PathBuf>,
+    /// Paths that were skipped because they could not be read or are
+    /// symlinks back to a directory above them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
 }
 
 impl ScanResult {
@@ -80,6 +235,9 @@ impl ScanResult {
                 tag_counts: HashMap::new(),
             },
             root: Some(root),
+            file_roots: HashMap::new(),
+            scanned: HashSet::new(),
+            errors: Vec::new(),
         }
     }
 
@@ -89,9 +247,14 @@ impl ScanResult {
             files_map: HashMap::new(),
             summary,
             root: None,
+            file_roots: HashMap::new(),
+            scanned: HashSet::new(),
+            errors: Vec::new(),
         }
     }
 
+    /// Whether the result holds no items. Files scanned without a match do
+    /// not count.
     pub fn is_empty(&self) -> bool {
         if let Some(files) = &self.files {
             files.is_empty()
@@ -102,6 +265,7 @@ impl ScanResult {
 
     pub fn add_file(&mut self, path: PathBuf, items: Vec<TodoItem>) {
         self.summary.files_scanned += 1;
+        self.scanned.insert(path.clone());
 
         if !items.is_empty() {
             self.summary.files_with_todos += 1;
@@ -115,13 +279,116 @@ impl ScanResult {
         }
     }
 
-    pub fn all_items(&self) -> Vec<(PathBuf, TodoItem)> {
-        let mut items = Vec::new();
-        for (path, file_items) in &self.files_map {
-            for item in file_items {
-                items.push((path.clone(), item.clone()));
+    /// Fold another scan into this one, recording which root each file came from.
+    ///
+    /// Every path `other` scanned takes its incoming items, even when it now
+    /// has none, and is counted once in `files_scanned`.
+    pub fn merge(&mut self, other: ScanResult) {
+        let incoming: HashSet<PathBuf> = other
+            .scanned
+            .iter()
+            .chain(other.files_map.keys())
+            .cloned()
+            .collect();
+        let rescanned = incoming
+            .iter()
+            .filter(|path| self.scanned.contains(*path) || self.files_map.contains_key(*path))
+            .count();
+        self.summary.files_scanned =
+            (self.summary.files_scanned + other.summary.files_scanned).saturating_sub(rescanned);
+        self.errors.extend(other.errors);
+        for path in &incoming {
+            self.remove_items(path);
+        }
+        self.scanned.extend(incoming);
+
+        for (path, items) in other.files_map {
+            let origin = other
+                .file_roots
+                .get(&path)
+                .cloned()
+                .or_else(|| other.root.clone());
+
+            self.summary.files_with_todos += 1;
+            self.summary.total_count += items.len();
+            for item in &items {
+                *self.summary.tag_counts.entry(item.tag.clone()).or_insert(0) += 1;
+            }
+
+            match origin {
+                Some(origin) => {
+                    self.file_roots.insert(path.clone(), origin);
+                }
+                None => {
+                    self.file_roots.remove(&path);
+                }
+            }
+            self.files_map.insert(path, items);
+        }
+    }
+
+    /// Drop `path`'s items and take them out of the summary, leaving
+    /// `files_scanned` alone.
+    fn remove_items(&mut self, path: &Path) {
+        self.file_roots.remove(path);
+        let Some(previous) = self.files_map.remove(path) else {
+            return;
+        };
+        self.summary.files_with_todos -= 1;
+        self.summary.total_count -= previous.len();
+        for item in &previous {
+            if let Some(count) = self.summary.tag_counts.get_mut(&item.tag) {
+                *count -= 1;
+                if *count == 0 {
+                    self.summary.tag_counts.remove(&item.tag);
+                }
             }
         }
+    }
+
+    /// Root a file was scanned from: its merged origin, or this result's root.
+    pub fn root_of(&self, path: &Path) -> Option<&Path> {
+        self.file_roots
+            .get(path)
+            .or(self.root.as_ref())
+            .map(PathBuf::as_path)
+    }
+
+    /// Every item with its path, borrowed and in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &TodoItem)> {
+        self.files_map
+            .iter()
+            .flat_map(|(path, items)| items.iter().map(move |item| (path.as_path(), item)))
+    }
+
+    /// Whether any item has exactly `priority`.
+    pub fn has_priority(&self, priority: Priority) -> bool {
+        self.iter().any(|(_, item)| item.priority == priority)
+    }
+
+    /// Owned copy of [`ScanResult::iter`].
+    pub fn all_items(&self) -> Vec<(PathBuf, TodoItem)> {
+        self.iter()
+            .map(|(path, item)| (path.to_path_buf(), item.clone()))
+            .collect()
+    }
+
+    /// Every item across all files, ordered by `key`.
+    pub fn sorted_items(&self, key: SortKey) -> Vec<(PathBuf, TodoItem)> {
+        let mut items = self.all_items();
+        items.sort_by(|(a_path, a), (b_path, b)| {
+            let primary = match key {
+                SortKey::Path => Ordering::Equal,
+                SortKey::Line => a.line.cmp(&b.line),
+                SortKey::Priority => b.priority.cmp(&a.priority),
+                SortKey::Tag => a.tag.cmp(&b.tag),
+                SortKey::Age => a.age_key().cmp(&b.age_key()),
+            };
+            primary
+                .then_with(|| a_path.cmp(b_path))
+                .then_with(|| a.line.cmp(&b.line))
+                .then_with(|| a.column.cmp(&b.column))
+        });
         items
     }
 
@@ -132,14 +399,189 @@ impl ScanResult {
     }
 
     pub fn filter_by_tag(&self, tag: &str) -> ScanResult {
+        self.filter_items(|_, item| item.tag.eq_ignore_ascii_case(tag))
+    }
+
+    /// The id [`ScanResult::assign_ids`] stored on an item, or for an item
+    /// without one, its [`TodoItem::stable_id`] among the items of this result.
+    pub fn item_id(&self, path: &Path, item: &TodoItem) -> String {
+        if let Some(id) = &item.id {
+            return id.clone();
+        }
+        let occurrence = self.files_map.get(path).map_or(0, |items| {
+            items
+                .iter()
+                .filter(|other| {
+                    (other.line, other.column) < (item.line, item.column)
+                        && other.tag == item.tag
+                        && other.message == item.message
+                })
+                .count()
+        });
+        item.stable_id(&self.id_path(path), occurrence)
+    }
+
+    /// Store every item's [`TodoItem::stable_id`], numbering identical items
+    /// of a file in line order. Call it on the full scan, before filtering,
+    /// so each item keeps the id it was resolved under.
+    pub fn assign_ids(&mut self) {
+        let paths: Vec<PathBuf> = self.files_map.keys().cloned().collect();
+        for path in paths {
+            let id_path = self.id_path(&path);
+            let items = self.files_map.get_mut(&path).expect("path from files_map");
+            let mut order: Vec<usize> = (0..items.len()).collect();
+            order.sort_by_key(|&idx| (items[idx].line, items[idx].column));
+
+            let mut seen: HashMap<(String, String), usize> = HashMap::new();
+            for idx in order {
+                let item = &mut items[idx];
+                let occurrence = seen
+                    .entry((item.tag.clone(), item.message.clone()))
+                    .or_insert(0);
+                item.id = Some(item.stable_id(&id_path, *occurrence));
+                *occurrence += 1;
+            }
+        }
+    }
+
+    /// `path` relative to this result's root, as ids use it. Files merged in
+    /// from other roots still use this one, so the same file gets the same
+    /// id however the scan named it.
+    fn id_path(&self, path: &Path) -> String {
+        self.root
+            .as_deref()
+            .and_then(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Per canonical tag, how many items were written in a different case.
+    pub fn case_deviations(&self) -> BTreeMap<String, usize> {
+        let mut deviations = BTreeMap::new();
+        for item in self.files_map.values().flatten() {
+            if item.matched_tag.is_some() {
+                *deviations.entry(item.tag.clone()).or_insert(0) += 1;
+            }
+        }
+        deviations
+    }
+
+    /// Hash of every item's [`ScanResult::item_id`], independent of file and
+    /// item order. Equal fingerprints mean the same set of items.
+    pub fn fingerprint(&self) -> String {
+        let mut ids: Vec<String> = self
+            .files_map
+            .iter()
+            .flat_map(|(path, items)| items.iter().map(|item| self.item_id(path, item)))
+            .collect();
+        ids.sort();
+        format!("{:016x}", fnv1a_64(ids.join("\n").as_bytes()))
+    }
+
+    /// Drop items whose [`ScanResult::item_id`] is in `ids`.
+    pub fn without_ids(&self, ids: &HashSet<String>) -> ScanResult {
+        self.filter_items(|path, item| !ids.contains(&self.item_id(path, item)))
+    }
+
+    /// Keep items assigned to `author`, matching any of their assignees.
+    pub fn filter_by_author(&self, author: &str) -> ScanResult {
+        self.filter_items(|_, item| item.has_author(author))
+    }
+
+    /// Keep items whose priority is at least `threshold`.
+    pub fn filter_by_min_priority(&self, threshold: Priority) -> ScanResult {
+        self.filter_items(|_, item| item.priority >= threshold)
+    }
+
+    /// Keep items due strictly before `today`.
+    pub fn overdue(&self, today: NaiveDate) -> ScanResult {
+        self.filter_items(|_, item| item.due.is_some_and(|due| due < today))
+    }
+
+    /// Keep `count` items picked at random. The same `seed` always picks the
+    /// same items from the same result.
+    pub fn sample(&self, count: usize, seed: u64) -> ScanResult {
+        let mut items = self.sorted_items(SortKey::Path);
+        let count = count.min(items.len());
+        let mut rng = SplitMix64::new(seed);
+
+        // Partial Fisher-Yates: the first `count` slots end up as the sample.
+        for i in 0..count {
+            let j = i + rng.below(items.len() - i);
+            items.swap(i, j);
+        }
+
+        let picked: HashSet<(PathBuf, usize, usize)> = items
+            .into_iter()
+            .take(count)
+            .map(|(path, item)| (path, item.line, item.column))
+            .collect();
+        self.filter_items(|path, item| {
+            picked.contains(&(path.to_path_buf(), item.line, item.column))
+        })
+    }
+
+    /// Keep the first `limit` items in `key` order.
+    pub fn limited(&self, key: SortKey, limit: usize) -> ScanResult {
+        let kept: HashSet<(PathBuf, usize, usize)> = self
+            .sorted_items(key)
+            .into_iter()
+            .take(limit)
+            .map(|(path, item)| (path, item.line, item.column))
+            .collect();
+        self.filter_items(|path, item| kept.contains(&(path.to_path_buf(), item.line, item.column)))
+    }
+
+    /// Collapse items with the same tag and message into the first one by
+    /// path and line, which records the other locations in `duplicates`.
+    pub fn deduplicated(&self) -> ScanResult {
+        let mut representatives: HashMap<(String, String), (PathBuf, usize, usize)> =
+            HashMap::new();
+        let mut duplicates: HashMap<(PathBuf, usize, usize), Vec<ItemLocation>> = HashMap::new();
+
+        for (path, item) in self.sorted_items(SortKey::Path) {
+            let key = (item.tag.clone(), item.message.clone());
+            match representatives.get(&key) {
+                Some(kept) => duplicates
+                    .entry(kept.clone())
+                    .or_default()
+                    .push(ItemLocation {
+                        path,
+                        line: item.line,
+                    }),
+                None => {
+                    representatives.insert(key, (path, item.line, item.column));
+                }
+            }
+        }
+
+        let kept: HashSet<&(PathBuf, usize, usize)> = representatives.values().collect();
+        let mut result = self.filter_items(|path, item| {
+            kept.contains(&(path.to_path_buf(), item.line, item.column))
+        });
+        for (path, items) in &mut result.files_map {
+            for item in items {
+                if let Some(others) = duplicates.remove(&(path.clone(), item.line, item.column)) {
+                    item.duplicates = others;
+                }
+            }
+        }
+        result
+    }
+
+    /// Keep the items `predicate` accepts, with the summary recounted.
+    pub fn filter_items<F>(&self, predicate: F) -> ScanResult
+    where
+        F: Fn(&Path, &TodoItem) -> bool,
+    {
         let root = self.root.clone().unwrap_or_else(|| PathBuf::from("."));
         let mut result = ScanResult::new(root);
-        result.summary.files_scanned = self.summary.files_scanned;
 
         for (path, items) in &self.files_map {
             let filtered: Vec<TodoItem> = items
                 .iter()
-                .filter(|item| item.tag.eq_ignore_ascii_case(tag))
+                .filter(|item| predicate(path, item))
                 .cloned()
                 .collect();
 
@@ -148,6 +590,15 @@ impl ScanResult {
             }
         }
 
+        result.summary.files_scanned = self.summary.files_scanned;
+        result.scanned = self.scanned.clone();
+        result.file_roots = self
+            .file_roots
+            .iter()
+            .filter(|(path, _)| result.files_map.contains_key(*path))
+            .map(|(path, root)| (path.clone(), root.clone()))
+            .collect();
+        result.errors = self.errors.clone();
         result
     }
 
@@ -168,6 +619,9 @@ impl ScanResult {
             files_map: HashMap::new(),
             summary: self.summary.clone(),
             root: None,
+            file_roots: HashMap::new(),
+            scanned: HashSet::new(),
+            errors: self.errors.clone(),
         }
     }
 
@@ -179,3 +633,427 @@ impl ScanResult {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(tag: &str, authors: &[&str]) -> TodoItem {
+        let authors: Vec<String> = authors.iter().map(|a| a.to_string()).collect();
+        TodoItem {
+            tag: tag.to_string(),
+            message: "message".to_string(),
+            line: 1,
+            column: 1,
+            author: authors.first().cloned(),
+            authors,
+            priority: Priority::from_tag(tag),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn merge_attributes_files_to_their_root() {
+        let mut first = ScanResult::new(PathBuf::from("/repo-a"));
+        first.add_file(PathBuf::from("/repo-a/lib.rs"), vec![item("TODO", &[])]);
+        let mut second = ScanResult::new(PathBuf::from("/repo-b"));
+        second.add_file(
+            PathBuf::from("/repo-b/main.rs"),
+            vec![item("BUG", &[]), item("TODO", &[])],
+        );
+        second.add_file(PathBuf::from("/repo-b/empty.rs"), vec![]);
+
+        first.merge(second);
+
+        assert_eq!(
+            first.root_of(Path::new("/repo-a/lib.rs")),
+            Some(Path::new("/repo-a"))
+        );
+        assert_eq!(
+            first.root_of(Path::new("/repo-b/main.rs")),
+            Some(Path::new("/repo-b"))
+        );
+        assert_eq!(first.summary.total_count, 3);
+        assert_eq!(first.summary.files_with_todos, 2);
+        assert_eq!(first.summary.files_scanned, 3);
+        assert_eq!(first.summary.tag_counts.get("TODO"), Some(&2));
+    }
+
+    #[test]
+    fn sorted_items_by_priority_puts_critical_first() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        let mut note = item("NOTE", &[]);
+        note.line = 1;
+        let mut todo = item("TODO", &[]);
+        todo.line = 2;
+        let mut bug = item("BUG", &[]);
+        bug.line = 3;
+        result.add_file(PathBuf::from("/repo/b.rs"), vec![note, todo.clone(), bug]);
+        result.add_file(PathBuf::from("/repo/a.rs"), vec![todo]);
+
+        let order: Vec<(String, usize)> = result
+            .sorted_items(SortKey::Priority)
+            .into_iter()
+            .map(|(path, item)| (format!("{}:{}", path.display(), item.tag), item.line))
+            .collect();
+
+        assert_eq!(
+            order,
+            vec![
+                ("/repo/b.rs:BUG".to_string(), 3),
+                ("/repo/a.rs:TODO".to_string(), 2),
+                ("/repo/b.rs:TODO".to_string(), 2),
+                ("/repo/b.rs:NOTE".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn emptiness_ignores_files_without_items() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(PathBuf::from("/repo/empty.rs"), vec![]);
+        assert!(result.is_empty());
+        assert!(result.summary.is_empty());
+
+        result.add_file(PathBuf::from("/repo/a.rs"), vec![item("NOTE", &[])]);
+        assert!(!result.is_empty());
+        assert!(!result.summary.is_empty());
+    }
+
+    #[test]
+    fn has_priority_matches_exactly() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        assert!(!result.has_priority(Priority::Low));
+
+        result.add_file(
+            PathBuf::from("/repo/a.rs"),
+            vec![item("TODO", &[]), item("BUG", &[])],
+        );
+        assert!(result.has_priority(Priority::Medium));
+        assert!(result.has_priority(Priority::Critical));
+        assert!(!result.has_priority(Priority::Low));
+        assert!(!result.has_priority(Priority::High));
+    }
+
+    #[test]
+    fn iter_borrows_every_item() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(
+            PathBuf::from("/repo/a.rs"),
+            vec![item("TODO", &[]), item("BUG", &[])],
+        );
+        result.add_file(PathBuf::from("/repo/b.rs"), vec![item("NOTE", &[])]);
+
+        assert_eq!(result.iter().count(), result.summary.total_count);
+        assert_eq!(
+            result
+                .iter()
+                .filter(|(path, _)| *path == Path::new("/repo/a.rs"))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn display_is_a_compact_line_with_optional_author() {
+        let mut todo = item("TODO", &[]);
+        todo.line = 3;
+        todo.column = 4;
+        todo.message = "share me".to_string();
+        assert_eq!(todo.to_string(), "TODO[Medium] 3:4: share me");
+
+        let mut assigned = item("FIXME", &["alice", "bob"]);
+        assigned.message = "race".to_string();
+        assert_eq!(
+            assigned.to_string(),
+            "FIXME(alice, bob)[Critical] 1:1: race"
+        );
+    }
+
+    #[test]
+    fn sorted_items_by_age_puts_oldest_first_and_undated_last() {
+        let dated = |line: usize, year: i32| TodoItem {
+            line,
+            blame_date: NaiveDate::from_ymd_opt(year, 1, 1)
+                .map(|date| date.and_hms_opt(0, 0, 0).unwrap().and_utc()),
+            ..item("TODO", &[])
+        };
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(
+            PathBuf::from("/repo/a.rs"),
+            vec![item("TODO", &[]), dated(2, 2024)],
+        );
+        result.add_file(PathBuf::from("/repo/b.rs"), vec![dated(3, 2019)]);
+
+        let lines: Vec<usize> = result
+            .sorted_items(SortKey::Age)
+            .into_iter()
+            .map(|(_, item)| item.line)
+            .collect();
+
+        assert_eq!(lines, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn filter_by_min_priority_drops_lower_items() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(
+            PathBuf::from("/repo/a.rs"),
+            vec![item("NOTE", &[]), item("TODO", &[]), item("BUG", &[])],
+        );
+        result.add_file(PathBuf::from("/repo/b.rs"), vec![item("TODO", &[])]);
+
+        let filtered = result.filter_by_min_priority(Priority::High);
+
+        assert_eq!(filtered.summary.total_count, 1);
+        assert_eq!(filtered.summary.files_with_todos, 1);
+        assert_eq!(filtered.summary.files_scanned, 2);
+        assert_eq!(filtered.summary.tag_counts.len(), 1);
+        assert_eq!(filtered.summary.tag_counts.get("BUG"), Some(&1));
+    }
+
+    #[test]
+    fn stable_id_ignores_line_and_root() {
+        let mut moved = item("TODO", &[]);
+        moved.line = 40;
+
+        let mut first = ScanResult::new(PathBuf::from("/repo"));
+        first.add_file(PathBuf::from("/repo/src/a.rs"), vec![item("TODO", &[])]);
+        let mut second = ScanResult::new(PathBuf::from("/elsewhere/repo"));
+        second.add_file(PathBuf::from("/elsewhere/repo/src/a.rs"), vec![moved]);
+
+        let (path, todo) = &first.all_items()[0];
+        let (other_path, other_todo) = &second.all_items()[0];
+        assert_eq!(
+            first.item_id(path, todo),
+            second.item_id(other_path, other_todo)
+        );
+        assert_eq!(first.item_id(path, todo).len(), 16);
+        assert_ne!(
+            first.item_id(path, todo),
+            first.item_id(path, &item("FIXME", &[]))
+        );
+    }
+
+    #[test]
+    fn identical_items_in_a_file_get_distinct_ids() {
+        let mut second = item("TODO", &[]);
+        second.line = 9;
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(
+            PathBuf::from("/repo/src/a.rs"),
+            vec![item("TODO", &[]), second],
+        );
+
+        let items = result.all_items();
+        let first_id = result.item_id(&items[0].0, &items[0].1);
+        let second_id = result.item_id(&items[1].0, &items[1].1);
+        assert_ne!(first_id, second_id);
+        assert_eq!(first_id, items[0].1.stable_id("src/a.rs", 0));
+
+        let mut single = ScanResult::new(PathBuf::from("/repo"));
+        single.add_file(PathBuf::from("/repo/src/a.rs"), vec![item("TODO", &[])]);
+        assert_ne!(result.fingerprint(), single.fingerprint());
+    }
+
+    #[test]
+    fn merge_sums_disjoint_results() {
+        let mut first = ScanResult::new(PathBuf::from("/repo"));
+        first.add_file(
+            PathBuf::from("/repo/a.rs"),
+            vec![item("TODO", &[]), item("BUG", &[])],
+        );
+        first.add_file(PathBuf::from("/repo/empty.rs"), vec![]);
+        let mut second = ScanResult::new(PathBuf::from("/repo"));
+        second.add_file(PathBuf::from("/repo/b.rs"), vec![item("TODO", &[])]);
+
+        first.merge(second);
+
+        assert_eq!(first.files_map.len(), 2);
+        assert_eq!(first.summary.total_count, 3);
+        assert_eq!(first.summary.files_with_todos, 2);
+        assert_eq!(first.summary.files_scanned, 3);
+        assert_eq!(first.summary.tag_counts.get("TODO"), Some(&2));
+        assert_eq!(first.summary.tag_counts.get("BUG"), Some(&1));
+    }
+
+    #[test]
+    fn merge_prefers_incoming_items_for_the_same_path() {
+        let mut cached = ScanResult::new(PathBuf::from("/repo"));
+        cached.add_file(
+            PathBuf::from("/repo/a.rs"),
+            vec![item("TODO", &[]), item("BUG", &[])],
+        );
+        cached.add_file(PathBuf::from("/repo/b.rs"), vec![item("NOTE", &[])]);
+        let mut fresh = ScanResult::new(PathBuf::from("/repo"));
+        fresh.add_file(PathBuf::from("/repo/a.rs"), vec![item("FIXME", &[])]);
+
+        cached.merge(fresh);
+
+        assert_eq!(cached.files_map[Path::new("/repo/a.rs")].len(), 1);
+        assert_eq!(cached.summary.total_count, 2);
+        assert_eq!(cached.summary.files_with_todos, 2);
+        assert_eq!(cached.summary.files_scanned, 2);
+        assert_eq!(cached.summary.tag_counts.get("FIXME"), Some(&1));
+        assert_eq!(cached.summary.tag_counts.get("NOTE"), Some(&1));
+        assert!(!cached.summary.tag_counts.contains_key("TODO"));
+        assert!(!cached.summary.tag_counts.contains_key("BUG"));
+    }
+
+    #[test]
+    fn merge_drops_stale_items_for_paths_rescanned_empty() {
+        let mut cached = ScanResult::new(PathBuf::from("/repo"));
+        cached.add_file(PathBuf::from("/repo/a.rs"), vec![item("TODO", &[])]);
+        cached.add_file(PathBuf::from("/repo/b.rs"), vec![item("NOTE", &[])]);
+        cached.add_file(PathBuf::from("/repo/empty.rs"), vec![]);
+        let mut fresh = ScanResult::new(PathBuf::from("/repo"));
+        fresh.add_file(PathBuf::from("/repo/a.rs"), vec![]);
+        fresh.add_file(PathBuf::from("/repo/empty.rs"), vec![]);
+
+        cached.merge(fresh);
+
+        assert!(!cached.files_map.contains_key(Path::new("/repo/a.rs")));
+        assert_eq!(cached.summary.total_count, 1);
+        assert_eq!(cached.summary.files_with_todos, 1);
+        assert_eq!(cached.summary.files_scanned, 3);
+        assert!(!cached.summary.tag_counts.contains_key("TODO"));
+        assert_eq!(cached.summary.tag_counts.get("NOTE"), Some(&1));
+    }
+
+    #[test]
+    fn sample_is_reproducible_for_a_seed() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        for file in 0..5 {
+            let items = (1..=10)
+                .map(|line| {
+                    let mut todo = item("TODO", &[]);
+                    todo.line = line;
+                    todo
+                })
+                .collect();
+            result.add_file(PathBuf::from(format!("/repo/{file}.rs")), items);
+        }
+
+        let first = result.sample(7, 42);
+        let again = result.sample(7, 42);
+
+        assert_eq!(first.summary.total_count, 7);
+        assert_eq!(first.summary.files_scanned, 5);
+        assert_eq!(
+            first.sorted_items(SortKey::Path),
+            again.sorted_items(SortKey::Path)
+        );
+        assert_ne!(
+            first.sorted_items(SortKey::Path),
+            result.sample(7, 43).sorted_items(SortKey::Path)
+        );
+        assert_eq!(result.sample(500, 1).summary.total_count, 50);
+    }
+
+    #[test]
+    fn overdue_keeps_only_items_due_before_today() {
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let due = |on: Option<&str>, line: usize| TodoItem {
+            due: on.map(date),
+            line,
+            ..item("TODO", &[])
+        };
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(
+            PathBuf::from("/repo/a.rs"),
+            vec![
+                due(Some("2024-12-30"), 1),
+                due(Some("2024-12-31"), 2),
+                due(Some("2025-01-01"), 3),
+                due(None, 4),
+            ],
+        );
+
+        let overdue = result.overdue(date("2024-12-31"));
+
+        let lines: Vec<usize> = overdue
+            .sorted_items(SortKey::Path)
+            .iter()
+            .map(|(_, item)| item.line)
+            .collect();
+        assert_eq!(lines, [1]);
+        assert_eq!(overdue.summary.total_count, 1);
+    }
+
+    #[test]
+    fn fingerprint_ignores_order_but_not_content() {
+        let todo = |message: &str| TodoItem {
+            message: message.to_string(),
+            ..item("TODO", &[])
+        };
+        let mut first = ScanResult::new(PathBuf::from("/repo"));
+        first.add_file(PathBuf::from("/repo/a.rs"), vec![todo("one"), todo("two")]);
+        first.add_file(PathBuf::from("/repo/b.rs"), vec![todo("three")]);
+        let mut reordered = ScanResult::new(PathBuf::from("/repo"));
+        reordered.add_file(PathBuf::from("/repo/b.rs"), vec![todo("three")]);
+        reordered.add_file(PathBuf::from("/repo/a.rs"), vec![todo("two"), todo("one")]);
+
+        assert_eq!(first.fingerprint(), reordered.fingerprint());
+        assert_eq!(first.fingerprint().len(), 16);
+
+        reordered.add_file(PathBuf::from("/repo/c.rs"), vec![todo("four")]);
+        assert_ne!(first.fingerprint(), reordered.fingerprint());
+    }
+
+    #[test]
+    fn deduplicated_collapses_the_same_message_across_files() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        for file in ["a", "b", "c"] {
+            result.add_file(
+                PathBuf::from(format!("/repo/{file}.rs")),
+                vec![item("TODO", &[])],
+            );
+        }
+        result.add_file(PathBuf::from("/repo/d.rs"), vec![item("FIXME", &[])]);
+
+        let deduped = result.deduplicated();
+
+        assert_eq!(deduped.summary.total_count, 2);
+        assert_eq!(deduped.summary.files_with_todos, 2);
+        assert_eq!(deduped.summary.files_scanned, 4);
+        assert_eq!(deduped.summary.tag_counts.get("TODO"), Some(&1));
+        let kept = Path::new("/repo/a.rs");
+        let todo = &deduped.files_map[kept][0];
+        assert_eq!(todo.seen_in_files(kept), 3);
+        assert_eq!(
+            todo.duplicates,
+            [
+                ItemLocation {
+                    path: PathBuf::from("/repo/b.rs"),
+                    line: 1
+                },
+                ItemLocation {
+                    path: PathBuf::from("/repo/c.rs"),
+                    line: 1
+                },
+            ]
+        );
+        assert!(
+            deduped.files_map[Path::new("/repo/d.rs")][0]
+                .duplicates
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn filter_by_author_matches_any_assignee() {
+        let mut result = ScanResult::new(PathBuf::from("/repo"));
+        result.add_file(
+            PathBuf::from("/repo/a.rs"),
+            vec![item("TODO", &["alice", "bob"]), item("FIXME", &["carol"])],
+        );
+        result.add_file(PathBuf::from("/repo/b.rs"), vec![item("TODO", &[])]);
+
+        let filtered = result.filter_by_author("Bob");
+
+        assert_eq!(filtered.summary.total_count, 1);
+        assert_eq!(filtered.summary.files_with_todos, 1);
+        assert_eq!(filtered.summary.files_scanned, 2);
+        assert_eq!(filtered.summary.tag_counts.get("TODO"), Some(&1));
+    }
+}